@@ -1,12 +1,131 @@
-use clap::Parser;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use clap::{Parser, Subcommand, ValueEnum};
+use crate::display_reports::Device;
+use crate::hardware::is_steam_deck;
 
 #[derive(Parser)]
+#[clap(args_conflicts_with_subcommands = true)]
 pub(crate) struct Args {
+    #[clap(subcommand)]
+    pub(crate) command: Option<Command>,
     pub(crate) query: Vec<String>,
     #[clap(short, long, default_value_t = 5, help = "Number of results to display")]
     pub(crate) count: u8,
     #[clap(short, long, default_value_t = 5, help = "Number of reports to display")]
     pub(crate) reports: u8,
+    #[clap(short, long, help = "Pick one of several matching games from a list, then show its reports (only in a terminal)")]
+    pub(crate) interactive: bool,
     #[clap(short = 'I', long, help = "Show game images (using \"viuer\" crate, full resolution images are displayed only in some terminals)")]
-    pub(crate) images: bool
+    pub(crate) images: bool,
+    #[clap(short, long, global = true, help = "Rank and highlight reports from systems similar to this one")]
+    pub(crate) similar: bool,
+    #[clap(long, global = true, default_value = "/", help = "Root directory used to detect local hardware (procfs, sysfs and /etc)")]
+    pub(crate) sysroot: PathBuf,
+    #[clap(short, long, global = true, help = "Only show reports from this kind of device [default: steam-deck when running on a Steam Deck, otherwise all]")]
+    pub(crate) device: Option<DeviceFilter>,
+    #[clap(long, global = true, help = "Steam installation directory [default: detected from ~/.steam, ~/.local/share/Steam and the Flatpak install]")]
+    pub(crate) steam_dir: Option<PathBuf>,
+    #[clap(long, global = true, value_enum, default_value_t = Prefer::Native, help = "Which rating to show first for games that also have a native Linux version")]
+    pub(crate) prefer: Prefer,
+    #[clap(long, help = "Show aggregate statistics of all reports instead of individual reports")]
+    pub(crate) stats: bool,
+    #[clap(long, help = "Show a chart of verdicts per month instead of individual reports")]
+    pub(crate) trend: bool,
+    #[clap(short, long, help = "Show the most common launch options across all reports instead of individual reports")]
+    pub(crate) launch_options: bool,
+    #[clap(short, long, help = "Show protontricks commands for the winetricks verbs mentioned in report notes instead of individual reports")]
+    pub(crate) protontricks: bool,
+    #[clap(short, long, help = "Show every detail of each report, including the reporter's full system specs")]
+    pub(crate) verbose: bool,
+    #[clap(long, help = "List report fields and values this version doesn't recognize, to spot changes in ProtonDB's data")]
+    pub(crate) debug_schema: bool,
+    // detected on first use, reading sysfs once per run
+    #[clap(skip)]
+    steam_deck: OnceLock<bool>
+}
+
+impl Args {
+    pub(crate) fn on_steam_deck(&self) -> bool {
+        *self.steam_deck.get_or_init(|| is_steam_deck(&self.sysroot))
+    }
+
+    pub(crate) fn device_filter(&self) -> DeviceFilter {
+        self.device.unwrap_or(if self.on_steam_deck() { DeviceFilter::SteamDeck } else { DeviceFilter::All })
+    }
+}
+
+#[derive(Subcommand)]
+pub(crate) enum Command {
+    #[clap(about = "Suggest the Proton version with the best success rate for a game")]
+    Recommend {
+        query: Vec<String>
+    },
+    #[clap(about = "Rate every game installed in the local Steam library")]
+    Library,
+    #[clap(about = "List installed Proton builds and custom compatibility tools, optionally with their success rate for a game")]
+    Tools {
+        query: Vec<String>
+    },
+    #[clap(about = "Write the launch options used in a report into the local Steam config")]
+    ApplyLaunchOptions {
+        app_id: u32,
+        #[clap(long, help = "ID of the report to take the launch options from")]
+        from_report: String,
+        #[clap(long, help = "Steam user ID (folder name in userdata) to apply the launch options for [default: all users]")]
+        user: Option<String>,
+        #[clap(long, help = "Write the launch options after showing them (without it they are only shown)")]
+        yes: bool
+    },
+    #[clap(about = "Group installed games into Steam collections by ProtonDB tier and Steam Deck verification")]
+    Collections {
+        #[clap(long, help = "Steam user ID (folder name in userdata) to write the collections for [default: all users]")]
+        user: Option<String>,
+        #[clap(long, help = "Only show the changes without writing them")]
+        dry_run: bool
+    },
+    #[clap(about = "Browse games and reports in a full-screen interface")]
+    Tui {
+        query: Vec<String>
+    },
+    #[clap(about = "Compare games side by side (quote names with spaces)")]
+    Compare {
+        #[clap(required = true, num_args = 2.., help = "Game names or Steam app IDs")]
+        games: Vec<String>
+    },
+    #[clap(about = "Look for known problems in a Proton log (PROTON_LOG=1) and show reports that address them")]
+    AnalyzeLog {
+        #[clap(help = "Path to a steam-<appid>.log file, or an app ID to use ~/steam-<appid>.log")]
+        log: String,
+        #[clap(long, help = "App ID of the game [default: taken from the log file name]")]
+        app_id: Option<u32>
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, strum_macros::Display)]
+pub(crate) enum DeviceFilter {
+    All,
+    #[strum(serialize = "PC")]
+    Pc,
+    #[strum(serialize = "Steam Deck")]
+    SteamDeck,
+    #[strum(serialize = "ChromeOS")]
+    ChromeOs
+}
+
+impl DeviceFilter {
+    pub fn matches(&self, device: &Device) -> bool {
+        match self {
+            DeviceFilter::All => true,
+            DeviceFilter::Pc => device.hardware_type != "steamDeck" && device.inferred.chrome_os.is_none(),
+            DeviceFilter::SteamDeck => device.hardware_type == "steamDeck",
+            DeviceFilter::ChromeOs => device.inferred.chrome_os.is_some(),
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, PartialEq)]
+pub(crate) enum Prefer {
+    Native,
+    Proton
 }
@@ -0,0 +1,141 @@
+use chrono::{Datelike, Months, NaiveDate};
+use colored::{ColoredString, Colorize};
+use crate::display_reports::Report;
+use crate::stats::{percentage, print_heading};
+
+pub(crate) struct MonthVerdicts {
+    pub month: NaiveDate,
+    pub works: usize,
+    pub fails: usize,
+}
+
+impl MonthVerdicts {
+    pub fn total(&self) -> usize {
+        self.works + self.fails
+    }
+
+    pub fn success_rate(&self) -> Option<f64> {
+        if self.total() == 0 { None } else { Some(self.works as f64 / self.total() as f64) }
+    }
+}
+
+fn first_of_month(timestamp: u32) -> NaiveDate {
+    let date = chrono::DateTime::from_timestamp(timestamp as i64, 0).unwrap().date_naive();
+    date.with_day(1).unwrap()
+}
+
+// one entry per month between the oldest and newest report, including months without reports
+pub(crate) fn verdicts_per_month(reports: &[Report]) -> Vec<MonthVerdicts> {
+    let (Some(first), Some(last)) = (
+        reports.iter().map(|report| report.timestamp).min(),
+        reports.iter().map(|report| report.timestamp).max()
+    ) else {
+        return Vec::new();
+    };
+
+    let mut months = Vec::new();
+    let mut month = first_of_month(first);
+    while month <= first_of_month(last) {
+        months.push(MonthVerdicts { month, works: 0, fails: 0 });
+        month = month.checked_add_months(Months::new(1)).unwrap();
+    }
+
+    for report in reports {
+        let month = first_of_month(report.timestamp);
+        let entry = months.iter_mut().find(|entry| entry.month == month).unwrap();
+        if report.outcome().works() {
+            entry.works += 1;
+        } else {
+            entry.fails += 1;
+        }
+    }
+
+    months
+}
+
+fn colored_by_rate(text: &str, rate: f64) -> ColoredString {
+    if rate >= 0.75 {
+        text.green()
+    } else if rate >= 0.5 {
+        text.yellow()
+    } else {
+        text.red()
+    }
+}
+
+fn sparkline(months: &[MonthVerdicts]) -> String {
+    const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    months.iter()
+        .map(|month| match month.success_rate() {
+            Some(rate) => colored_by_rate(&SPARKS[(rate * 7.0).round() as usize].to_string(), rate).to_string(),
+            None => " ".to_string(),
+        })
+        .collect()
+}
+
+fn success_rate_of(months: &[MonthVerdicts]) -> Option<f64> {
+    let works: usize = months.iter().map(|month| month.works).sum();
+    let total: usize = months.iter().map(|month| month.total()).sum();
+    if total == 0 { None } else { Some(works as f64 / total as f64) }
+}
+
+pub(crate) fn display_trend(reports: &[Report], terminal_width: u16) {
+    const MAX_BAR_MONTHS: usize = 12;
+    const RECENT_MONTHS: usize = 3;
+    const LABEL_WIDTH: usize = 9;
+
+    println!("{}", "—".repeat(terminal_width as usize));
+
+    let months = verdicts_per_month(reports);
+    if months.is_empty() {
+        println!("{}", "No reports to chart".truecolor(120, 120, 120).italic());
+        return;
+    }
+
+    // "Success rate: YYYY-MM " before and " YYYY-MM" after the sparkline
+    let spark_width = (terminal_width as usize).saturating_sub(30).max(1);
+    let spark_months = &months[months.len().saturating_sub(spark_width)..];
+    println!("{} {} {} {}",
+        "Success rate:".truecolor(200, 200, 200),
+        spark_months.first().unwrap().month.format("%Y-%m"),
+        sparkline(spark_months),
+        months.last().unwrap().month.format("%Y-%m")
+    );
+
+    // compare the most recent months against everything before them to spot regressions
+    let recent_start = months.len().saturating_sub(RECENT_MONTHS);
+    if let (Some(before), Some(recent)) = (success_rate_of(&months[..recent_start]), success_rate_of(&months[recent_start..])) {
+        let change = (recent - before) * 100.0;
+        let trend = if change <= -15.0 {
+            "Regressed".red()
+        } else if change >= 15.0 {
+            "Improved".green()
+        } else {
+            "Stable".normal()
+        };
+        println!("{} {} {}",
+            "Trend:".truecolor(200, 200, 200),
+            trend.bold(),
+            format!("({}% in the last {} months, {}% before)", (recent * 100.0).round(), RECENT_MONTHS, (before * 100.0).round()).truecolor(120, 120, 120)
+        );
+    }
+
+    print_heading("Verdicts per Month");
+    let bar_months = &months[months.len().saturating_sub(MAX_BAR_MONTHS)..];
+    let max = bar_months.iter().map(|month| month.total()).max().unwrap_or(0);
+    let bar_width = (terminal_width as usize).saturating_sub(LABEL_WIDTH + 16).max(10);
+    for month in bar_months {
+        let works = (month.works * bar_width).checked_div(max).unwrap_or(0);
+        let fails = (month.fails * bar_width).checked_div(max).unwrap_or(0);
+        println!("  {:<LABEL_WIDTH$}{}{} {}",
+            month.month.format("%Y-%m").to_string(),
+            "█".repeat(works.max(month.works.min(1))).green(),
+            "█".repeat(fails.max(month.fails.min(1))).red(),
+            if month.total() == 0 {
+                "-".truecolor(120, 120, 120)
+            } else {
+                format!("{}/{} ({}%)", month.works, month.total(), percentage(month.works, month.total())).truecolor(120, 120, 120)
+            }
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use colored::Colorize;
+use reqwest::Client;
+use serde_json::{json, Value};
+use crate::args::Args;
+use crate::library::{fetch_ratings, installed_games, steam_roots};
+use crate::tier::{DeckStatus, Tier};
+use crate::steam_config::{is_steam_running, print_diff, user_config_dirs, write_atomically, write_with_backup};
+
+// collections live in the user's cloud storage namespace, which Steam syncs across machines
+const NAMESPACE_FILE: &str = "cloudstorage/cloud-storage-namespace-1.json";
+
+pub(crate) struct Collection {
+    pub id: String,
+    pub name: String,
+    pub app_ids: Vec<u32>,
+}
+
+pub(crate) struct CollectionChange {
+    pub name: String,
+    pub old: Option<Vec<u32>>,
+    pub new: Vec<u32>,
+}
+
+pub(crate) struct NamespaceChange {
+    pub path: PathBuf,
+    pub namespace: Value,
+    pub changes: Vec<CollectionChange>,
+}
+
+// stable IDs so running the command again updates the same collections instead of adding new ones
+fn collection_id(name: &str) -> String {
+    let slug: String = name.to_lowercase().chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect();
+    format!("uc-{}", slug.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-"))
+}
+
+fn collection(name: String, mut app_ids: Vec<u32>) -> Collection {
+    app_ids.sort();
+    Collection { id: collection_id(&name), name, app_ids }
+}
+
+pub(crate) fn tier_collections(ratings: &[(u32, Option<Tier>, Option<DeckStatus>)]) -> Vec<Collection> {
+    let mut collections: Vec<Collection> = Tier::RATED.iter()
+        .map(|tier| {
+            let app_ids = ratings.iter()
+                .filter(|(_, rating, _)| rating.as_ref() == Some(tier))
+                .map(|(app_id, _, _)| *app_id)
+                .collect();
+            collection(format!("ProtonDB: {}", tier), app_ids)
+        })
+        .collect();
+
+    let verified = ratings.iter()
+        .filter(|(_, _, status)| *status == Some(DeckStatus::Verified))
+        .map(|(app_id, _, _)| *app_id)
+        .collect();
+    collections.push(collection("Deck Verified".to_string(), verified));
+    collections
+}
+
+fn parse_app_ids(value: &Value) -> Vec<u32> {
+    let mut app_ids: Vec<u32> = value.as_array()
+        .map(|app_ids| app_ids.iter().filter_map(|app_id| app_id.as_u64()).map(|app_id| app_id as u32).collect())
+        .unwrap_or_default();
+    app_ids.sort();
+    app_ids
+}
+
+// the namespace file is a list of [key, entry] pairs whose "value" is itself a JSON encoded string
+pub(crate) fn plan_collections(path: &Path, collections: &[Collection]) -> Result<(Value, Vec<CollectionChange>), String> {
+    let mut namespace = match fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text).map_err(|error| format!("could not parse {}: {}", path.display(), error))?,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => json!([]),
+        Err(error) => return Err(format!("could not read {}: {}", path.display(), error)),
+    };
+    let Some(entries) = namespace.as_array_mut() else {
+        return Err(format!("could not parse {}: expected a list of entries", path.display()));
+    };
+
+    let mut version = entries.iter()
+        .filter_map(|entry| entry.get(1)?.get("version")?.as_str()?.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0);
+    let timestamp = chrono::Utc::now().timestamp();
+
+    let mut changes = Vec::new();
+    for collection in collections {
+        let key = format!("user-collections.{}", collection.id);
+        let position = entries.iter().position(|entry| entry.get(0).and_then(|key| key.as_str()) == Some(key.as_str()));
+
+        let old_value = position
+            .and_then(|position| entries[position].get(1)?.get("value")?.as_str())
+            .and_then(|value| serde_json::from_str::<Value>(value).ok());
+        let old = old_value.as_ref().map(|value| parse_app_ids(&value["added"]));
+        if old.as_ref() == Some(&collection.app_ids) || (old.is_none() && collection.app_ids.is_empty()) {
+            continue;
+        }
+
+        // the union merge would keep games that left a collection on other machines unless they are listed as removed
+        let mut removed: Vec<u32> = old_value.iter()
+            .flat_map(|value| parse_app_ids(&value["added"]).into_iter().chain(parse_app_ids(&value["removed"])))
+            .filter(|app_id| !collection.app_ids.contains(app_id))
+            .collect();
+        removed.sort();
+        removed.dedup();
+
+        version += 1;
+        let value = json!({ "id": collection.id, "name": collection.name, "added": collection.app_ids, "removed": removed });
+        let entry = json!([key, {
+            "key": key,
+            "timestamp": timestamp,
+            "value": value.to_string(),
+            "version": version.to_string(),
+            "conflictResolutionMethod": "custom",
+            "strMethodId": "union-collections"
+        }]);
+        match position {
+            Some(position) => entries[position] = entry,
+            None => entries.push(entry),
+        }
+        changes.push(CollectionChange { name: collection.name.clone(), old, new: collection.app_ids.clone() });
+    }
+
+    Ok((namespace, changes))
+}
+
+// reads and updates every user's namespace file before anything is written, so one broken file leaves all of them untouched
+pub(crate) fn plan_all_collections(config_dirs: &[PathBuf], collections: &[Collection]) -> Result<Vec<NamespaceChange>, String> {
+    config_dirs.iter()
+        .map(|config_dir| {
+            let path = config_dir.join(NAMESPACE_FILE);
+            let (namespace, changes) = plan_collections(&path, collections)?;
+            Ok(NamespaceChange { path, namespace, changes })
+        })
+        .collect()
+}
+
+fn write_namespace(path: &Path, contents: &str) -> std::io::Result<Option<PathBuf>> {
+    if path.exists() {
+        write_with_backup(path, contents.as_bytes()).map(Some)
+    } else {
+        fs::create_dir_all(path.parent().unwrap())?;
+        write_atomically(path, contents.as_bytes()).map(|_| None)
+    }
+}
+
+pub(crate) async fn write_collections(args: &Args, client: &Client, user: Option<&str>, dry_run: bool) -> Result<(), String> {
+    let steam_roots = steam_roots(args);
+    let config_dirs = user_config_dirs(&steam_roots, user);
+    if config_dirs.is_empty() {
+        return Err("no Steam user config found (use --steam-dir and --user to point to one)".to_string());
+    }
+    if !dry_run && is_steam_running(&args.sysroot) {
+        return Err("Steam is running, close it first so it doesn't overwrite the change".to_string());
+    }
+
+    let games = installed_games(&steam_roots);
+    if games.is_empty() {
+        println!("No installed games found");
+        return Ok(());
+    }
+
+    let (summaries, hits) = fetch_ratings(client, &games).await;
+    let ratings: Vec<(u32, Option<Tier>, Option<DeckStatus>)> = games.iter().zip(summaries).zip(hits)
+        .map(|((game, summary), hit)| (game.app_id, summary.map(|summary| summary.tier), hit.and_then(|hit| DeckStatus::from_oslist(&hit.oslist))))
+        .collect();
+    let collections = tier_collections(&ratings);
+
+    let games_line = |name: &str, app_ids: &[u32]| format!("{} ({} {})", name, app_ids.len(), if app_ids.len() == 1 { "game" } else { "games" });
+    for plan in plan_all_collections(&config_dirs, &collections)? {
+        if plan.changes.is_empty() {
+            println!("{} {}", plan.path.display().to_string().bold(), "collections are up to date".truecolor(120, 120, 120));
+            continue;
+        }
+
+        let old: Vec<String> = plan.changes.iter()
+            .filter_map(|change| change.old.as_ref().map(|old| games_line(&change.name, old)))
+            .collect();
+        let new: Vec<String> = plan.changes.iter().map(|change| games_line(&change.name, &change.new)).collect();
+        print_diff(&plan.path, &old, &new);
+
+        if dry_run { continue }
+        let backup = write_namespace(&plan.path, &serde_json::to_string(&plan.namespace).unwrap())
+            .map_err(|error| format!("could not write {}: {}", plan.path.display(), error))?;
+        if let Some(backup) = backup {
+            println!("{}", format!("Backup written to {}", backup.display()).truecolor(120, 120, 120));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir;
+
+    const FIXTURE: &str = "tests/fixtures/steam/userdata/12345/config";
+
+    fn value(namespace: &Value, id: &str) -> Option<Value> {
+        let key = format!("user-collections.{}", id);
+        let entry = namespace.as_array()?.iter().find(|entry| entry[0] == key.as_str())?;
+        serde_json::from_str(entry[1]["value"].as_str()?).ok()
+    }
+
+    #[test]
+    fn names_collections_by_tier_and_deck_status() {
+        let ratings = [
+            (620, Some(Tier::Platinum), Some(DeckStatus::Verified)),
+            (292030, Some(Tier::Gold), Some(DeckStatus::Playable)),
+            (10, Some(Tier::Gold), None),
+            (20, None, Some(DeckStatus::Verified)),
+        ];
+        let collections = tier_collections(&ratings);
+        let find = |id: &str| collections.iter().find(|collection| collection.id == id).unwrap();
+        assert_eq!(find("uc-protondb-platinum").app_ids, [620]);
+        assert_eq!(find("uc-protondb-gold").app_ids, [10, 292030]);
+        assert_eq!(find("uc-protondb-gold").name, "ProtonDB: Gold");
+        assert!(find("uc-protondb-borked").app_ids.is_empty());
+        assert_eq!(find("uc-deck-verified").app_ids, [20, 620]);
+    }
+
+    #[test]
+    fn lists_games_that_left_a_collection_as_removed() {
+        let path = PathBuf::from(FIXTURE).join(NAMESPACE_FILE);
+        let collections = tier_collections(&[(620, Some(Tier::Gold), None), (292030, Some(Tier::Gold), None)]);
+        let (namespace, changes) = plan_collections(&path, &collections).unwrap();
+
+        // 1091500 dropped out of Gold and 620 moved there from Platinum
+        let gold = value(&namespace, "uc-protondb-gold").unwrap();
+        assert_eq!(gold["added"], json!([620, 292030]));
+        assert_eq!(gold["removed"], json!([10, 1091500]));
+        let platinum = value(&namespace, "uc-protondb-platinum").unwrap();
+        assert_eq!(platinum["added"], json!([]));
+        assert_eq!(platinum["removed"], json!([620]));
+        // collections that never had games aren't created
+        assert_eq!(value(&namespace, "uc-protondb-silver"), None);
+
+        // the user's own collections are left alone and changed entries get a newer version
+        assert_eq!(value(&namespace, "favorite").unwrap()["added"], json!([1091500]));
+        let versions: Vec<&str> = namespace.as_array().unwrap().iter().filter_map(|entry| entry[1]["version"].as_str()).collect();
+        assert_eq!(versions, ["3", "9", "8"]);
+
+        let names: Vec<&str> = changes.iter().map(|change| change.name.as_str()).collect();
+        assert_eq!(names, ["ProtonDB: Platinum", "ProtonDB: Gold"]);
+        assert_eq!(changes[1].old, Some(vec![292030, 1091500]));
+    }
+
+    #[test]
+    fn leaves_up_to_date_collections_alone() {
+        let path = PathBuf::from(FIXTURE).join(NAMESPACE_FILE);
+        let collections = tier_collections(&[(620, Some(Tier::Platinum), None), (292030, Some(Tier::Gold), None), (1091500, Some(Tier::Gold), None)]);
+        let (_, changes) = plan_collections(&path, &collections).unwrap();
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn creates_a_missing_namespace_file() {
+        let collections = tier_collections(&[(620, Some(Tier::Platinum), Some(DeckStatus::Verified))]);
+        let (namespace, changes) = plan_collections(&test_dir("no-namespace").join(NAMESPACE_FILE), &collections).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(namespace.as_array().unwrap().len(), 2);
+        assert_eq!(value(&namespace, "uc-deck-verified").unwrap()["removed"], json!([]));
+    }
+
+    #[test]
+    fn plans_nothing_when_one_namespace_is_broken() {
+        let broken = test_dir("broken-namespace");
+        fs::create_dir_all(broken.join("cloudstorage")).unwrap();
+        fs::write(broken.join(NAMESPACE_FILE), "{\"not\": \"a list\"}").unwrap();
+        let collections = tier_collections(&[(620, Some(Tier::Gold), None)]);
+        assert!(plan_all_collections(&[PathBuf::from(FIXTURE), broken], &collections).is_err());
+        assert_eq!(plan_all_collections(&[PathBuf::from(FIXTURE)], &collections).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,99 @@
+use colored::Colorize;
+use futures::future::join_all;
+use futures::join;
+use reqwest::Client;
+use crate::args::Args;
+use crate::display_reports::{fetch_counts, fetch_reports_with_counts, Outcome, Report};
+use crate::recommend::recommend_proton;
+use crate::stats::{compute_stats, percentage};
+use crate::tier::{DeckStatus, Tier};
+use crate::utils::{fetch_summaries, get_colored_tier, is_native, resolve_game, visible_width, Counts, Summary};
+
+const ROWS: [&str; 8] = ["Rating", "Native", "Trending", "Steam Deck", "Reports", "Works OOB", "Top Faults", "Best Proton"];
+
+struct Column {
+    name: String,
+    cells: Vec<String>,
+}
+
+async fn fetch_column(args: &Args, client: &Client, query: &str, counts: &Counts) -> Result<Column, String> {
+    const TOP_FAULTS_COUNT: usize = 2;
+
+    let game = resolve_game(client, query).await.ok_or(format!("No games found for \"{}\"", query))?;
+    let app_id: u32 = game.object_id.parse().unwrap();
+    let (summaries, reports) = join!(
+        fetch_summaries(client, std::slice::from_ref(&game.object_id)),
+        fetch_reports_with_counts(client, app_id, counts)
+    );
+    let summary: Option<Summary> = summaries.into_iter().next().flatten();
+
+    let device = args.device_filter();
+    let reports: Vec<Report> = reports
+        .map(|reports| reports.reports.into_iter().filter(|report| device.matches(&report.device)).collect())
+        .unwrap_or_default();
+
+    let missing = || "-".truecolor(120, 120, 120).to_string();
+    let tier = summary.as_ref().map(|summary| summary.tier.clone()).unwrap_or(Tier::Pending);
+    let trending = summary.as_ref().map(|summary| summary.trending_tier.colored().to_string()).unwrap_or_else(missing);
+    let steam_deck = DeckStatus::from_oslist(&game.oslist).map(|status| status.colored().to_string()).unwrap_or_else(missing);
+
+    let out_of_the_box = reports.iter().filter(|report| report.outcome() == Outcome::WorksOutOfTheBox).count();
+    let works_oob = if reports.is_empty() {
+        missing()
+    } else {
+        format!("{}% ({}/{})", percentage(out_of_the_box, reports.len()), out_of_the_box, reports.len())
+    };
+
+    let faults: Vec<&str> = compute_stats(&reports).faults.iter().take(TOP_FAULTS_COUNT).map(|fault| fault.category).collect();
+    let top_faults = if faults.is_empty() { missing() } else { faults.join(", ").yellow().to_string() };
+    let best_proton = recommend_proton(&reports).into_iter().next().map(|choice| choice.name).unwrap_or_else(missing);
+
+    Ok(Column {
+        name: game.name,
+        cells: vec![
+            get_colored_tier(&tier, &game.oslist, args.prefer),
+            if is_native(&game.oslist) { "yes".green().to_string() } else { "no".truecolor(120, 120, 120).to_string() },
+            trending,
+            steam_deck,
+            reports.len().to_string(),
+            works_oob,
+            top_faults,
+            best_proton,
+        ],
+    })
+}
+
+fn pad(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(visible_width(text))))
+}
+
+pub(crate) async fn compare(args: &Args, client: &Client, queries: &[String]) {
+    let counts = fetch_counts(client).await;
+    let columns = join_all(queries.iter().map(|query| fetch_column(args, client, query, &counts))).await;
+
+    let columns: Vec<Column> = columns.into_iter()
+        .filter_map(|column| column.map_err(|error| println!("{}", error.truecolor(120, 120, 120).italic())).ok())
+        .collect();
+    if columns.is_empty() {
+        return;
+    }
+
+    let label_width = ROWS.iter().map(|row| row.len()).max().unwrap_or(0) + 1;
+    let widths: Vec<usize> = columns.iter()
+        .map(|column| column.cells.iter().map(|cell| visible_width(cell)).chain([column.name.chars().count()]).max().unwrap_or(0))
+        .collect();
+
+    print!("{}", " ".repeat(label_width));
+    for (column, width) in columns.iter().zip(&widths) {
+        print!("  {}", pad(&column.name.bold().to_string(), *width));
+    }
+    println!();
+
+    for (index, row) in ROWS.iter().enumerate() {
+        print!("{}", pad(&format!("{}:", row).truecolor(200, 200, 200).to_string(), label_width));
+        for (column, width) in columns.iter().zip(&widths) {
+            print!("  {}", pad(&column.cells[index], *width));
+        }
+        println!();
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use colored::Colorize;
+use reqwest::Client;
+use crate::args::Args;
+use crate::display_reports::fetch_reports;
+use crate::library::{library_folders, steam_roots};
+use crate::recommend::recommend_proton;
+use crate::stats::percentage;
+use crate::utils::resolve_game;
+use crate::vdf;
+
+pub(crate) struct InstalledTool {
+    // name as it appears in reports and Steam's settings, e.g. "Proton 9.0" or "GE-Proton9-4"
+    pub name: String,
+    pub display_name: String,
+    pub version: Option<String>,
+    pub path: PathBuf,
+    pub custom: bool,
+}
+
+// compatibility tools forced per app in Steam's settings, keyed by app ID ("0" is the default for all other titles)
+pub(crate) fn compat_tool_mapping(steam_root: &Path) -> HashMap<String, String> {
+    let Ok(text) = fs::read_to_string(steam_root.join("config/config.vdf")) else { return HashMap::new() };
+    let Ok(config) = vdf::parse(&text) else { return HashMap::new() };
+    let Some(mapping) = config.get_path(&["InstallConfigStore", "Software", "Valve", "Steam", "CompatToolMapping"]) else {
+        return HashMap::new();
+    };
+
+    mapping.entries().iter()
+        .filter_map(|(app_id, tool)| {
+            let name = tool.get("name")?.as_str()?;
+            if name.is_empty() { return None }
+            Some((app_id.clone(), name.to_string()))
+        })
+        .collect()
+}
+
+// turns internal tool names like "proton_9" or "proton_513" into the names used in reports
+pub(crate) fn tool_display_name(name: &str) -> String {
+    match name.strip_prefix("proton_") {
+        Some("experimental") => "Proton Experimental".to_string(),
+        Some("hotfix") => "Proton Hotfix".to_string(),
+        Some(version) if !version.is_empty() && version.chars().all(|c| c.is_ascii_digit()) => {
+            // there was never a Proton 1.x, so a leading 1 means a two digit major version
+            let (major, minor) = version.split_at(if version.starts_with('1') { 2.min(version.len()) } else { 1 });
+            format!("Proton {}.{}", major, if minor.is_empty() { "0" } else { minor })
+        }
+        _ => name.to_string(),
+    }
+}
+
+fn proton_version(name: &str) -> Option<String> {
+    let version = name.to_lowercase().strip_prefix("proton ")?.to_string();
+    let mut parts = version.split(['.', '-']);
+    Some(format!("{}.{}", parts.next()?, parts.next().unwrap_or("0")))
+}
+
+// whether a configured tool belongs to the same Proton release as a version named in reports
+pub(crate) fn same_proton(configured: &str, reported: &str) -> bool {
+    let configured = tool_display_name(configured);
+    let (configured_lower, reported_lower) = (configured.to_lowercase(), reported.to_lowercase());
+    if configured_lower == reported_lower {
+        return true;
+    }
+    if configured_lower.contains("experimental") || reported_lower.contains("experimental") {
+        return configured_lower.contains("experimental") && reported_lower.contains("experimental");
+    }
+    // reports that only say "GE" without a version match any GE-Proton build
+    if reported_lower == "ge-proton" {
+        return configured_lower.starts_with("ge-proton") || configured_lower.starts_with("proton-ge");
+    }
+    match (proton_version(&configured), proton_version(reported)) {
+        (Some(configured), Some(reported)) => configured == reported,
+        _ => false,
+    }
+}
+
+fn read_version(tool_dir: &Path) -> Option<String> {
+    // official builds write "<timestamp> proton-9.0-2", custom builds usually just the name
+    let version = fs::read_to_string(tool_dir.join("version")).ok()?;
+    version.split_whitespace().last().map(|version| version.to_string())
+}
+
+fn official_tools(library: &Path) -> Vec<InstalledTool> {
+    let Ok(entries) = fs::read_dir(library.join("steamapps/common")) else { return Vec::new() };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("Proton") && entry.path().join("proton").is_file())
+        .map(|entry| {
+            let display_name = entry.file_name().to_string_lossy().to_string();
+            // "Proton - Experimental" and "Proton 9.0 (Beta)" are called "Proton Experimental" and "Proton 9.0" in reports
+            let name = display_name.replace(" - ", " ");
+            let name = name.split(" (").next().unwrap_or_default().to_string();
+            InstalledTool { name, display_name, version: read_version(&entry.path()), path: entry.path(), custom: false }
+        })
+        .collect()
+}
+
+fn custom_tools(compatibility_tools_dir: &Path) -> Vec<InstalledTool> {
+    let Ok(entries) = fs::read_dir(compatibility_tools_dir) else { return Vec::new() };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let text = fs::read_to_string(entry.path().join("compatibilitytool.vdf")).ok()?;
+            let manifest = vdf::parse(&text).ok()?;
+            let tools = manifest.get_path(&["compatibilitytools", "compat_tools"])?;
+            let tools: Vec<InstalledTool> = tools.entries().iter()
+                .map(|(name, tool)| InstalledTool {
+                    name: name.clone(),
+                    display_name: tool.get("display_name").and_then(|name| name.as_str()).unwrap_or(name).to_string(),
+                    version: read_version(&entry.path()),
+                    path: entry.path(),
+                    custom: true,
+                })
+                .collect();
+            Some(tools)
+        })
+        .flatten()
+        .collect()
+}
+
+pub(crate) fn installed_tools(steam_roots: &[PathBuf], sysroot: &Path) -> Vec<InstalledTool> {
+    let mut tools = Vec::new();
+    for steam_root in steam_roots {
+        for library in library_folders(steam_root) {
+            tools.extend(official_tools(&library));
+        }
+        tools.extend(custom_tools(&steam_root.join("compatibilitytools.d")));
+    }
+    tools.extend(custom_tools(&sysroot.join("usr/share/steam/compatibilitytools.d")));
+
+    let mut unique: Vec<InstalledTool> = Vec::new();
+    for tool in tools {
+        if !unique.iter().any(|other| other.name == tool.name) {
+            unique.push(tool);
+        }
+    }
+    unique.sort_by(|a, b| a.custom.cmp(&b.custom).then(a.name.cmp(&b.name)));
+    unique
+}
+
+pub(crate) fn find_installed<'a>(tools: &'a [InstalledTool], reported: &str) -> Option<&'a InstalledTool> {
+    tools.iter().find(|tool| same_proton(&tool.name, reported))
+}
+
+pub(crate) async fn display_tools(args: &Args, client: &Client, query: &str) {
+    let tools = installed_tools(&steam_roots(args), &args.sysroot);
+    if tools.is_empty() {
+        println!("No Proton builds or custom compatibility tools found");
+        return;
+    }
+
+    let choices = if query.is_empty() {
+        Vec::new()
+    } else {
+        let Some(game) = resolve_game(client, query).await else {
+            println!("No games found");
+            return;
+        };
+        println!("{}", game.name.bold());
+        println!();
+        let device = args.device_filter();
+        let mut reports = fetch_reports(client, game.object_id.parse().unwrap(), false).await.map(|reports| reports.reports).unwrap_or_default();
+        reports.retain(|report| device.matches(&report.device));
+        recommend_proton(&reports)
+    };
+
+    let name_width = tools.iter().map(|tool| tool.display_name.chars().count()).max().unwrap_or(0);
+    for tool in &tools {
+        let reports = choices.iter()
+            .filter(|choice| same_proton(&tool.name, &choice.name))
+            .fold((0, 0), |(reports, works), choice| (reports + choice.reports, works + choice.works));
+        println!("{:<name_width$}  {:<8}  {}",
+            tool.display_name.bold(),
+            if tool.custom { "custom" } else { "official" }.truecolor(120, 120, 120),
+            match (query.is_empty(), reports) {
+                (true, _) => tool.version.clone().unwrap_or_default().truecolor(120, 120, 120),
+                (false, (0, _)) => "no reports".truecolor(120, 120, 120),
+                (false, (reports, works)) => format!("{}/{} reports work ({}%)", works, reports, percentage(works, reports)).normal(),
+            }
+        );
+        if query.is_empty() {
+            println!("  {}", tool.path.display().to_string().truecolor(120, 120, 120));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_compat_tool_mapping() {
+        let mapping = compat_tool_mapping(Path::new("tests/fixtures/steam"));
+        assert_eq!(mapping.get("0").map(String::as_str), Some("proton_9"));
+        assert_eq!(mapping.get("1091500").map(String::as_str), Some("GE-Proton9-4"));
+        // an empty name means the game uses the default
+        assert_eq!(mapping.get("292030"), None);
+        assert!(compat_tool_mapping(Path::new("tests/fixtures/missing")).is_empty());
+    }
+
+    fn tools() -> Vec<InstalledTool> {
+        installed_tools(&[PathBuf::from("tests/fixtures/steam")], Path::new("tests/fixtures/sysroot"))
+    }
+
+    #[test]
+    fn finds_installed_tools() {
+        let tools = tools();
+        let names: Vec<(&str, &str, bool)> = tools.iter().map(|tool| (tool.name.as_str(), tool.display_name.as_str(), tool.custom)).collect();
+        // official builds from every library folder first, a directory without the proton script isn't a build;
+        // the copy of GE-Proton9-4 in Steam's own directory wins over the system-wide one
+        assert_eq!(names, [
+            ("Proton 8.0", "Proton 8.0", false),
+            ("Proton 9.0", "Proton 9.0", false),
+            ("Proton Experimental", "Proton - Experimental", false),
+            ("GE-Proton9-4", "GE-Proton9-4", true),
+            ("Proton-GE", "Proton-GE (system)", true),
+        ]);
+        assert_eq!(tools[3].path, Path::new("tests/fixtures/steam/compatibilitytools.d/GE-Proton9-4"));
+        assert!(installed_tools(&[PathBuf::from("tests/fixtures/missing")], Path::new("tests/fixtures/missing")).is_empty());
+    }
+
+    #[test]
+    fn reads_versions() {
+        assert_eq!(read_version(Path::new("tests/fixtures/steam/steamapps/common/Proton 9.0")).as_deref(), Some("proton-9.0-2"));
+        assert_eq!(read_version(Path::new("tests/fixtures/steam/compatibilitytools.d/GE-Proton9-4")).as_deref(), Some("GE-Proton9-4"));
+        assert_eq!(read_version(Path::new("tests/fixtures/missing")), None);
+        let tools = tools();
+        let versions: Vec<Option<&str>> = tools.iter().map(|tool| tool.version.as_deref()).collect();
+        assert_eq!(versions, [
+            Some("proton-8.0-5"), Some("proton-9.0-2"), Some("experimental-9.0-20240610"), Some("GE-Proton9-4"), Some("GE-Proton9-2"),
+        ]);
+    }
+
+    #[test]
+    fn finds_installed_versions_of_reported_ones() {
+        let tools = tools();
+        let installed = |reported: &str| find_installed(&tools, reported).map(|tool| tool.display_name.as_str());
+        assert_eq!(installed("Proton 9.0"), Some("Proton 9.0"));
+        // reports name point releases, installs only the major version
+        assert_eq!(installed("Proton 9.0-1"), Some("Proton 9.0"));
+        assert_eq!(installed("Proton 8.0-4"), Some("Proton 8.0"));
+        assert_eq!(installed("Proton Experimental"), Some("Proton - Experimental"));
+        assert_eq!(installed("GE-Proton"), Some("GE-Proton9-4"));
+        assert_eq!(installed("GE-Proton9-4"), Some("GE-Proton9-4"));
+        assert_eq!(installed("Proton 7.0-6"), None);
+        assert_eq!(installed("GE-Proton8-25"), None);
+    }
+
+    #[test]
+    fn matches_proton_versions() {
+        assert!(same_proton("proton_9", "Proton 9.0"));
+        assert!(same_proton("proton_9", "Proton 9.0-2"));
+        assert!(same_proton("Proton 8.0", "proton 8.0-5"));
+        assert!(same_proton("proton_513", "Proton 5.13-6"));
+        assert!(!same_proton("proton_9", "Proton 8.0"));
+        assert!(!same_proton("proton_9", "Proton 9.1"));
+
+        assert!(same_proton("proton_experimental", "Proton Experimental"));
+        assert!(!same_proton("proton_experimental", "Proton 9.0"));
+        assert!(!same_proton("proton_9", "Proton Experimental"));
+
+        // reports that only say GE match any GE build, whichever way it is named
+        assert!(same_proton("GE-Proton9-4", "GE-Proton"));
+        assert!(same_proton("Proton-GE", "GE-Proton"));
+        assert!(!same_proton("proton_9", "GE-Proton"));
+        assert!(same_proton("GE-Proton9-4", "GE-Proton9-4"));
+        assert!(!same_proton("GE-Proton9-4", "GE-Proton9-5"));
+    }
+
+    #[test]
+    fn names_tools_like_reports() {
+        assert_eq!(tool_display_name("proton_9"), "Proton 9.0");
+        assert_eq!(tool_display_name("proton_513"), "Proton 5.13");
+        assert_eq!(tool_display_name("proton_experimental"), "Proton Experimental");
+        assert_eq!(tool_display_name("GE-Proton9-4"), "GE-Proton9-4");
+    }
+}
@@ -1,11 +1,16 @@
+use std::sync::OnceLock;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use colored::Colorize;
 use futures::future::join_all;
+use futures::{stream, StreamExt};
 use image::DynamicImage;
 use chrono_humanize::HumanTime;
 use crate::args::Args;
-use crate::utils::{calculate_protondb_id, Counts, print_image};
+use crate::utils::{calculate_protondb_id, Counts, print_image, CONCURRENT_REQUESTS};
+use crate::hardware::{matching_specs, SIMILAR_SYSTEM_THRESHOLD};
+use crate::recommend::proton_name;
+use crate::schema::schema_issues;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub(crate) struct ChromeOS {
@@ -145,7 +150,7 @@ impl PerformanceFaults {
 fn deserialize_input_faults_other<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
     where D: serde::Deserializer<'de> {
     let s: serde_json::Value = Deserialize::deserialize(deserializer)?;
-    return if s["0"] == "on" {
+    if s["0"] == "on" {
         Ok(Some(false))
     } else if s == "other" {
         Ok(Some(true))
@@ -154,6 +159,13 @@ fn deserialize_input_faults_other<'de, D>(deserializer: D) -> Result<Option<bool
     }
 }
 
+// decodes optional details on their own so that an unexpected shape only loses that field instead of the whole report
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where D: serde::Deserializer<'de>, T: serde::de::DeserializeOwned {
+    let value: serde_json::Value = Deserialize::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
+}
+
 fn none() -> Option<bool> {
     None
 }
@@ -216,19 +228,19 @@ impl AudioFaults {
 
 #[derive(Serialize, Deserialize, Clone)]
 pub(crate) struct FollowUp {
-    #[serde(rename = "audioFaults")]
+    #[serde(rename = "audioFaults", default, deserialize_with = "lenient")]
     pub audio_faults: Option<AudioFaults>,
-    #[serde(rename = "graphicalFaults")]
+    #[serde(rename = "graphicalFaults", default, deserialize_with = "lenient")]
     pub graphical_faults: Option<GraphicalFaults>,
-    #[serde(rename = "inputFaults")]
+    #[serde(rename = "inputFaults", default, deserialize_with = "lenient")]
     pub input_faults: Option<InputFaults>,
-    #[serde(rename = "performanceFaults")]
+    #[serde(rename = "performanceFaults", default, deserialize_with = "lenient")]
     pub performance_faults: Option<PerformanceFaults>,
-    #[serde(rename = "windowingFaults")]
+    #[serde(rename = "windowingFaults", default, deserialize_with = "lenient")]
     pub windowing_faults: Option<WindowingFaults>,
-    #[serde(rename = "stabilityFaults")]
+    #[serde(rename = "stabilityFaults", default, deserialize_with = "lenient")]
     pub stability_faults: Option<StabilityFaults>,
-    #[serde(rename = "saveGameFaults")]
+    #[serde(rename = "saveGameFaults", default, deserialize_with = "lenient")]
     pub save_game_faults: Option<SaveGameFaults>,
 }
 
@@ -243,46 +255,108 @@ pub(crate) struct CustomizationsUsed {
     pub protontricks: Option<bool>,
 }
 
+// yes/no answers of the report form, keeping anything else as it was sent
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub(crate) enum Answer {
+    Yes,
+    No,
+    Other(String),
+}
+
+impl Answer {
+    pub fn is_yes(&self) -> bool {
+        *self == Answer::Yes
+    }
+}
+
+impl From<String> for Answer {
+    fn from(answer: String) -> Self {
+        match answer.as_str() {
+            "yes" => Answer::Yes,
+            "no" => Answer::No,
+            _ => Answer::Other(answer),
+        }
+    }
+}
+
+impl From<Answer> for String {
+    fn from(answer: Answer) -> Self {
+        match answer {
+            Answer::Yes => "yes".to_string(),
+            Answer::No => "no".to_string(),
+            Answer::Other(answer) => answer,
+        }
+    }
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Answer::Yes => write!(f, "Yes"),
+            Answer::No => write!(f, "No"),
+            Answer::Other(answer) => write!(f, "{}", answer),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, strum_macros::Display)]
+pub(crate) enum Outcome {
+    #[strum(serialize = "Works out of the box")]
+    WorksOutOfTheBox,
+    #[strum(serialize = "Works with tinkering")]
+    WorksWithTinkering,
+    #[strum(serialize = "Not recommended")]
+    NotRecommended,
+    Borked,
+}
+
+impl Outcome {
+    pub fn works(&self) -> bool {
+        matches!(self, Outcome::WorksOutOfTheBox | Outcome::WorksWithTinkering)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub(crate) struct Responses {
     #[serde(rename = "answerToWhatGame")]
     pub answer_to_what_game: String,
     #[serde(rename = "audioFaults")]
-    pub audio_faults: Option<String>,
+    pub audio_faults: Option<Answer>,
     #[serde(rename = "concludingNotes")]
     pub concluding_notes: Option<String>,
-    #[serde(rename = "customizationsUsed")]
+    #[serde(rename = "customizationsUsed", default, deserialize_with = "lenient")]
     pub customizations_used: Option<CustomizationsUsed>,
-    #[serde(rename = "followUp")]
+    #[serde(rename = "followUp", default, deserialize_with = "lenient")]
     pub follow_up: Option<FollowUp>,
     #[serde(rename = "graphicalFaults")]
-    pub graphical_faults: Option<String>,
+    pub graphical_faults: Option<Answer>,
     #[serde(rename = "inputFaults")]
-    pub input_faults: Option<String>,
-    pub installs: String,
+    pub input_faults: Option<Answer>,
+    pub installs: Answer,
     pub launcher: Option<String>,
     pub notes: Notes,
-    pub opens: String,
+    pub opens: Answer,
     #[serde(rename = "performanceFaults")]
-    pub performance_faults: Option<String>,
+    pub performance_faults: Option<Answer>,
     #[serde(rename = "saveGameFaults")]
-    pub save_game_faults: Option<String>,
+    pub save_game_faults: Option<Answer>,
     #[serde(rename = "significantBugs")]
-    pub significant_bugs: Option<String>,
+    pub significant_bugs: Option<Answer>,
     #[serde(rename = "stabilityFaults")]
-    pub stability_faults: Option<String>,
+    pub stability_faults: Option<Answer>,
     #[serde(rename = "startsPlay")]
-    pub starts_play: Option<String>,
+    pub starts_play: Option<Answer>,
     #[serde(rename = "tinkerOverride")]
-    pub tinker_override: Option<String>,
+    pub tinker_override: Option<Answer>,
     #[serde(rename = "triedOob")]
-    pub tried_oob: Option<String>,
+    pub tried_oob: Option<Answer>,
     pub variant: Option<String>,
-    pub verdict: String,
+    pub verdict: Answer,
     #[serde(rename = "verdictOob")]
-    pub verdict_oob: Option<String>,
+    pub verdict_oob: Option<Answer>,
     #[serde(rename = "windowingFaults")]
-    pub windowing_faults: Option<String>,
+    pub windowing_faults: Option<Answer>,
     #[serde(rename = "protonVersion")]
     pub proton_version: String,
     #[serde(rename = "customProtonVersion")]
@@ -290,10 +364,10 @@ pub(crate) struct Responses {
     #[serde(rename = "launchOptions")]
     pub launch_options: Option<String>,
     #[serde(rename = "batteryPerformance")]
-    pub battery_performance: Option<String>,
+    pub battery_performance: Option<Answer>,
     #[serde(rename = "didChangeControlLayout")]
-    pub did_change_control_layout: Option<String>,
-    pub readability: Option<String>,
+    pub did_change_control_layout: Option<Answer>,
+    pub readability: Option<Answer>,
     #[serde(rename = "secondaryLauncher")]
     pub secondary_launcher: Option<String>,
     #[serde(rename = "appSelectionMethod")]
@@ -325,46 +399,159 @@ pub(crate) struct Report {
     pub responses: Responses,
     pub timestamp: u32,
     pub device: Device,
+    // filled in on first use, views and filters ask for it once per report and redraw
+    #[serde(skip)]
+    outcome: OnceLock<Outcome>,
 }
 
-#[derive(Serialize, Deserialize)]
+impl Report {
+    // the verdict every view and filter goes by: a report works when the game opens and the reporter recommends it
+    pub fn outcome(&self) -> Outcome {
+        *self.outcome.get_or_init(|| match (&self.responses.opens, &self.responses.verdict) {
+            (Answer::Yes, Answer::Yes) if get_tinker_steps(self).is_empty() => Outcome::WorksOutOfTheBox,
+            (Answer::Yes, Answer::Yes) => Outcome::WorksWithTinkering,
+            (Answer::Yes, _) => Outcome::NotRecommended,
+            _ => Outcome::Borked,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
 pub(crate) struct Reports {
     pub page: u8,
     #[serde(rename = "perPage")]
     pub per_page: u8,
     pub reports: Vec<Report>,
     pub total: u16,
+    // reports decoded from the file, `reports` may be filtered down afterwards
+    #[serde(skip)]
+    pub decoded: usize,
+    // reports that could not be decoded at all
+    #[serde(skip)]
+    pub skipped: usize,
+    // fields and values this version doesn't know about, see --debug-schema
+    #[serde(skip)]
+    pub schema_issues: Vec<String>,
+}
+
+impl Reports {
+    // adds a further page, keeping the page details of the first
+    pub fn append(&mut self, page: Reports) {
+        self.reports.extend(page.reports);
+        self.decoded += page.decoded;
+        self.skipped += page.skipped;
+        self.schema_issues.extend(page.schema_issues);
+    }
+}
+
+#[derive(Deserialize)]
+struct RawReports {
+    page: u8,
+    #[serde(rename = "perPage")]
+    per_page: u8,
+    reports: Vec<serde_json::Value>,
+    total: u16,
+}
+
+// decodes every report separately so that one malformed report doesn't lose the whole game;
+// comparing each report with what was decoded from it is only worth it for --debug-schema
+pub(crate) fn parse_reports(text: &str, check_schema: bool) -> Option<Reports> {
+    let raw: RawReports = serde_json::from_str(text).ok()?;
+    let mut reports = Reports { page: raw.page, per_page: raw.per_page, total: raw.total, ..Default::default() };
+    for value in raw.reports {
+        let original = check_schema.then(|| value.clone());
+        match serde_json::from_value::<Report>(value) {
+            Ok(report) => {
+                if let Some(original) = original {
+                    schema_issues(&original, &serde_json::to_value(&report).unwrap(), "", &mut reports.schema_issues);
+                }
+                reports.reports.push(report);
+                reports.decoded += 1;
+            }
+            Err(error) => {
+                reports.skipped += 1;
+                reports.schema_issues.push(format!("skipped report: {}", error));
+            }
+        }
+    }
+    Some(reports)
 }
 
-pub(crate) async fn fetch_reports(client: &Client, steam_id: u32) -> Reports {
-    let counts = client
+pub(crate) async fn fetch_counts(client: &Client) -> Counts {
+    client
         .get("https://www.protondb.com/data/counts.json")
         .send().await.unwrap()
-        .json::<Counts>().await.unwrap();
+        .json::<Counts>().await.unwrap()
+}
 
-    let protondb_id = calculate_protondb_id(steam_id, counts.reports, counts.timestamp);
+async fn fetch_reports_text(client: &Client, steam_id: u32, counts: &Counts, page: u32) -> Option<String> {
+    let protondb_id = calculate_protondb_id(steam_id, counts.reports, counts.timestamp, page);
     client
         .get(format!("https://www.protondb.com/data/reports/all-devices/app/{}.json", protondb_id))
-        .send().await.unwrap()
-        .json::<Reports>().await.unwrap()
+        .send().await.ok()?
+        .error_for_status().ok()?
+        .text().await.ok()
+}
+
+// games without any reports have no reports file, so this returns None for them,
+// as it does when the file doesn't have the expected shape
+pub(crate) async fn fetch_reports_page(client: &Client, steam_id: u32, counts: &Counts, page: u32) -> Option<Reports> {
+    parse_reports(&fetch_reports_text(client, steam_id, counts, page).await?, false)
+}
+
+// every page up to `total`, for views that look at all reports rather than the newest ones
+pub(crate) async fn fetch_all_reports(client: &Client, steam_id: u32, check_schema: bool) -> Option<Reports> {
+    let counts = fetch_counts(client).await;
+    let mut reports = parse_reports(&fetch_reports_text(client, steam_id, &counts, 1).await?, check_schema)?;
+    if is_last_page(&reports, reports.reports.len()) || reports.per_page == 0 {
+        return Some(reports);
+    }
+
+    let pages = (reports.total as u32).div_ceil(reports.per_page as u32);
+    let further: Vec<Option<Reports>> = stream::iter(2..=pages)
+        .map(|page| {
+            let counts = &counts;
+            async move { parse_reports(&fetch_reports_text(client, steam_id, counts, page).await?, check_schema) }
+        })
+        .buffered(CONCURRENT_REQUESTS)
+        .collect()
+        .await;
+    // a missing page only makes the numbers less complete
+    for page in further.into_iter().flatten() {
+        reports.append(page);
+    }
+    Some(reports)
+}
+
+// whether `page` is the last one, with `loaded` reports fetched up to and including it
+pub(crate) fn is_last_page(page: &Reports, loaded: usize) -> bool {
+    page.reports.is_empty() || loaded >= page.total as usize
+}
+
+pub(crate) async fn fetch_reports_with_counts(client: &Client, steam_id: u32, counts: &Counts) -> Option<Reports> {
+    fetch_reports_page(client, steam_id, counts, 1).await
+}
+
+pub(crate) async fn fetch_reports(client: &Client, steam_id: u32, check_schema: bool) -> Option<Reports> {
+    let counts = fetch_counts(client).await;
+    parse_reports(&fetch_reports_text(client, steam_id, &counts, 1).await?, check_schema)
 }
 
-pub(crate) async fn fetch_avatars(client: &Client, avatar_urls: &Vec<String>) -> Vec<DynamicImage> {
-    let futures = avatar_urls.into_iter().map(|avatar_url| {
+pub(crate) async fn fetch_avatars(client: &Client, avatar_urls: &[String]) -> Vec<DynamicImage> {
+    let futures = avatar_urls.iter().map(|avatar_url| {
         let client = client.clone();
         async move {
             let image = client.get(avatar_url)
                 .send().await.unwrap()
                 .bytes().await.unwrap().to_vec();
             let image = image::load_from_memory(&image).unwrap();
-            let image = DynamicImage::ImageRgba8(image.to_rgba8());
-            image
+            DynamicImage::ImageRgba8(image.to_rgba8())
         }
     });
     join_all(futures).await
 }
 
-fn get_tinker_steps(report: &Report) -> Vec<String> {
+pub(crate) fn get_tinker_steps(report: &Report) -> Vec<String> {
     let mut tinker_steps = Vec::new();
 
     if let Some(launcher) = &report.responses.notes.launcher {
@@ -385,17 +572,140 @@ fn get_tinker_steps(report: &Report) -> Vec<String> {
         tinker_steps.push("Set launch options".to_string());
     }
 
-    if let Some(tinker_override) = &report.responses.tinker_override {
-        if tinker_override == "yes" {
-            tinker_steps.push("Other".to_string());
-        }
+    if report.responses.tinker_override.as_ref().is_some_and(Answer::is_yes) {
+        tinker_steps.push("Other".to_string());
     }
 
     tinker_steps
 }
 
-pub(crate) async fn display_reports(reports: Reports, args: &Args, client: &Client, terminal_width: u16) {
-    let avatars = fetch_avatars(client, &reports.reports.iter().map(|report| report.contributor.steam.avatar.clone()).collect()).await;
+impl CustomizationsUsed {
+    pub fn keys(&self) -> Vec<String> {
+        let mut customizations = Vec::new();
+        if self.config_change.unwrap_or(false) { customizations.push("Config Change".to_string()) }
+        if self.winetricks.unwrap_or(false) { customizations.push("Winetricks".to_string()) }
+        if self.protontricks.unwrap_or(false) { customizations.push("Protontricks".to_string()) }
+        if self.lutris.unwrap_or(false) { customizations.push("Lutris".to_string()) }
+        if self.media_foundation.unwrap_or(false) { customizations.push("Media Foundation".to_string()) }
+        customizations
+    }
+}
+
+fn format_hours(minutes: u16) -> String {
+    let hours = minutes / 60;
+    format!("{}{} hour{}", if hours == 0 { "< " } else { "" }, hours.max(1), if hours == 0 || hours == 1 { "" } else { "s" })
+}
+
+// every field of a report that the regular view leaves out, grouped as (section, [(label, value)])
+pub(crate) fn report_details(report: &Report) -> Vec<(&'static str, Vec<(&'static str, String)>)> {
+    let responses = &report.responses;
+    let answer = |answer: &Option<Answer>| answer.as_ref().map(|answer| answer.to_string());
+
+    let setup = vec![
+        ("Proton", Some(proton_name(responses))),
+        ("Variant", responses.variant.clone()),
+        ("Installs", Some(responses.installs.to_string())),
+        ("Opens", Some(responses.opens.to_string())),
+        ("Starts Play", answer(&responses.starts_play)),
+        ("Tried Out of the Box", answer(&responses.tried_oob)),
+        ("Works Out of the Box", answer(&responses.verdict_oob)),
+        ("Launcher", responses.launcher.clone()),
+        ("Secondary Launcher", responses.secondary_launcher.clone()),
+        ("App Selection Method", responses.app_selection_method.clone()),
+        ("Customizations Used", responses.customizations_used.as_ref().map(|customizations| customizations.keys().join(", ")).filter(|keys| !keys.is_empty())),
+    ];
+
+    let steam = &report.contributor.steam;
+    let contributor = vec![
+        ("Nickname", Some(steam.nickname.clone())),
+        ("Owns Game", Some(if steam.owned { "Yes" } else { "No" }.to_string())),
+        ("Playtime", Some(format_hours(steam.playtime))),
+        ("Playtime on Linux", steam.playtime_linux.map(format_hours)),
+        ("Reports", Some(report.contributor.report_tally.to_string())),
+        ("Report ID", Some(report.id.clone())),
+    ];
+
+    let specs = &report.device.inferred.steam;
+    let mut system = vec![
+        ("Device", Some(report.device.hardware_type.clone())),
+        ("OS", Some(specs.os.clone())),
+        ("Kernel", Some(specs.kernel.clone())),
+        ("CPU", Some(specs.cpu.clone())),
+        ("RAM", Some(specs.ram.clone())),
+        ("GPU", Some(specs.gpu.clone())),
+        ("GPU Driver", Some(specs.gpu_driver.clone())),
+        ("Steam Runtime", specs.steam_runtime_version.clone()),
+        ("Window Manager", specs.x_window_manager.clone()),
+    ];
+    if let Some(chrome_os) = &report.device.inferred.chrome_os {
+        system.extend([
+            ("ChromeOS Board", Some(chrome_os.board.clone())),
+            ("ChromeOS Channel", Some(chrome_os.channel.clone())),
+            ("ChromeOS Version", Some(chrome_os.chrome_version.clone())),
+            ("ChromeOS Platform", Some(chrome_os.platform.clone())),
+        ]);
+    }
+
+    [("Setup", setup), ("Contributor", contributor), ("System", system)].into_iter()
+        .map(|(section, fields)| {
+            let fields = fields.into_iter()
+                .filter_map(|(label, value)| Some((label, value.filter(|value| !value.is_empty())?)))
+                .collect();
+            (section, fields)
+        })
+        .collect()
+}
+
+// every fault a report lists with its details and note, each group preceded by a blank line
+pub(crate) fn fault_lines(responses: &Responses) -> Vec<String> {
+    const DETAILS_NOT_PROVIDED: &str = "details not provided";
+
+    let mut lines = Vec::new();
+    let label = |label: &str, value: String| format!("{} {}", format!("{}:", label).truecolor(200, 200, 200), value);
+    let details_not_provided = || DETAILS_NOT_PROVIDED.truecolor(120, 120, 120).italic().to_string();
+
+    macro_rules! follow_up_faults {
+        ($faults:ident, $label:expr) => {
+            if responses.$faults.as_ref().is_some_and(Answer::is_yes) {
+                let faults = responses.follow_up.as_ref()
+                    .and_then(|follow_up| follow_up.$faults.as_ref())
+                    .map(|faults| faults.keys())
+                    .filter(|faults| !faults.is_empty());
+                lines.push(String::new());
+                lines.push(label($label, faults.map(|faults| faults.join(", ").yellow().to_string()).unwrap_or_else(details_not_provided)));
+                if let Some(note) = responses.notes.$faults.as_ref().filter(|note| !note.is_empty()) {
+                    lines.push(note.clone());
+                }
+            }
+        };
+    }
+
+    follow_up_faults!(audio_faults, "Audio");
+    follow_up_faults!(graphical_faults, "Graphics");
+    follow_up_faults!(windowing_faults, "Windowing");
+    follow_up_faults!(input_faults, "Input");
+    follow_up_faults!(save_game_faults, "Save Game");
+    follow_up_faults!(performance_faults, "Performance");
+    follow_up_faults!(stability_faults, "Stability");
+
+    // significant bugs have no follow-up questions, only a note
+    if responses.significant_bugs.as_ref().is_some_and(Answer::is_yes) {
+        lines.push(String::new());
+        lines.push(label("Significant Bugs", "Yes".yellow().to_string()));
+        lines.push(responses.notes.significant_bugs.clone().filter(|note| !note.is_empty()).unwrap_or_else(details_not_provided));
+    }
+
+    lines
+}
+
+pub(crate) async fn display_reports(reports: Reports, args: &Args, client: &Client, terminal_width: u16, local_specs: Option<&Specs>) {
+    let mut reports = reports.reports;
+    if let Some(local_specs) = local_specs {
+        reports.sort_by_key(|report| std::cmp::Reverse(matching_specs(local_specs, &report.device.inferred.steam).len()));
+    }
+    reports.truncate(args.reports as usize);
+
+    let avatars = fetch_avatars(client, &reports.iter().map(|report| report.contributor.steam.avatar.clone()).collect::<Vec<_>>()).await;
 
     const IMAGE_WIDTH: u32 = 7;
     const IMAGE_HEIGHT: u32 = 3;
@@ -420,8 +730,6 @@ pub(crate) async fn display_reports(reports: Reports, args: &Args, client: &Clie
         };
     }
 
-    let max_index = reports.reports.len().min(args.reports as usize);
-    let reports = reports.reports[0..max_index].to_vec();
     for (index, report) in reports.iter().enumerate() {
         lines_printed = 0;
 
@@ -432,44 +740,39 @@ pub(crate) async fn display_reports(reports: Reports, args: &Args, client: &Clie
         let timestamp = chrono::DateTime::from_timestamp(report.timestamp as i64, 0).unwrap();
         label!(HumanTime::from(timestamp).to_string().truecolor(120, 120, 120).italic());
 
-        let hours = report.contributor.steam.playtime / 60;
         label!(format!("{} {} | {}",
             report.contributor.steam.nickname,
             format!("({} reports)", report.contributor.report_tally).truecolor(120, 120, 120),
-            format!("{}{} hour{} overall",
-                if hours == 0 { "< " } else { "" },
-                hours.max(1),
-                if hours == 0 || hours == 1 { "" } else { "s" }
-            ).truecolor(200, 200, 200)
+            format!("{} overall", format_hours(report.contributor.steam.playtime)).truecolor(200, 200, 200)
         ));
 
-        let tinker_steps = get_tinker_steps(&report);
+        if let Some(local_specs) = local_specs {
+            let matches = matching_specs(local_specs, &report.device.inferred.steam);
+            if matches.len() >= SIMILAR_SYSTEM_THRESHOLD {
+                label!("Similar System", format!("same {}", matches.join(", ")).cyan());
+            }
+        }
 
-        if report.responses.opens == "yes" {
-            if tinker_steps.is_empty() {
-                if report.responses.verdict == "yes" {
-                    label!("Recommended".green());
-                } else {
-                    label!("Not Recommended".yellow());
-                }
-            } else {
-                if let Some(verdict_oob) = &report.responses.verdict_oob {
-                    if verdict_oob == "yes" {
-                        label!("Recommended".green());
-                    } else {
-                        label!("Not recommended".yellow());
-                    }
-                }
+        let tinker_steps = get_tinker_steps(report);
 
-                if report.responses.verdict == "yes" {
-                    label!("Recommended (Tinker)".green());
+        // tinkered reports also say whether the game worked before tinkering
+        let outcome = report.outcome();
+        if outcome != Outcome::Borked && !tinker_steps.is_empty() {
+            if let Some(verdict_oob) = &report.responses.verdict_oob {
+                if verdict_oob.is_yes() {
+                    label!("Recommended".green());
                 } else {
-                    label!("Not Recommended (Tinker)".yellow());
+                    label!("Not recommended".yellow());
                 }
             }
-        } else {
-            label!("Borked".red());
         }
+        label!(match outcome {
+            Outcome::WorksOutOfTheBox => "Recommended".green(),
+            Outcome::WorksWithTinkering => "Recommended (Tinker)".green(),
+            Outcome::NotRecommended if tinker_steps.is_empty() => "Not Recommended".yellow(),
+            Outcome::NotRecommended => "Not Recommended (Tinker)".yellow(),
+            Outcome::Borked => "Borked".red(),
+        });
 
         let mut print_newline = false;
 
@@ -478,9 +781,9 @@ pub(crate) async fn display_reports(reports: Reports, args: &Args, client: &Clie
             print_newline = true;
         }
 
-        if report.responses.opens == "no" {
-            label!("Installs", if report.responses.installs == "yes" { "Yes" } else { "No" }.yellow());
-            label!("Opens", if report.responses.opens == "yes" { "Yes" } else { "No" }.yellow());
+        if outcome == Outcome::Borked {
+            label!("Installs", report.responses.installs.to_string().yellow());
+            label!("Opens", report.responses.opens.to_string().yellow());
         }
 
         if !tinker_steps.is_empty() {
@@ -493,6 +796,17 @@ pub(crate) async fn display_reports(reports: Reports, args: &Args, client: &Clie
             print_newline = true;
         }
 
+        let steam_deck_answers = [
+            ("Battery Performance", &report.responses.battery_performance),
+            ("Text Readability", &report.responses.readability),
+            ("Changed Control Layout", &report.responses.did_change_control_layout),
+        ];
+        for (name, answer) in steam_deck_answers {
+            if let Some(answer) = answer {
+                label!(name, answer);
+            }
+        }
+
         if let Some(tinker_override) = &report.responses.notes.tinker_override {
             if !tinker_override.is_empty() {
                 if print_newline { label!(); }
@@ -500,42 +814,10 @@ pub(crate) async fn display_reports(reports: Reports, args: &Args, client: &Clie
             }
         }
 
-        macro_rules! print_faults_follow_up {
-            ($faults:ident, $label:expr) => {
-                if let Some(faults) = &report.responses.$faults {
-                    if faults == "yes" {
-                        label!();
-                        let faults = report.responses.follow_up.as_ref().unwrap().$faults.as_ref().unwrap().keys();
-                        label!($label, faults.join(", ").yellow());
-                        if let Some(note) = report.responses.notes.$faults.as_ref() {
-                            label!(note);
-                        }
-                    }
-                }
-            };
-        }
-
-        macro_rules! print_faults {
-            ($faults:ident, $label:expr) => {
-                if let Some(faults) = &report.responses.$faults {
-                    if faults == "yes" {
-                        label!();
-                        label!($label, "Yes".yellow());
-                        label!(report.responses.notes.$faults.as_ref().unwrap());
-                    }
-                }
-            };
+        for line in fault_lines(&report.responses) {
+            label!(line);
         }
 
-        print_faults_follow_up!(audio_faults, "Audio");
-        print_faults_follow_up!(graphical_faults, "Graphics");
-        print_faults_follow_up!(windowing_faults, "Windowing");
-        print_faults_follow_up!(input_faults, "Input");
-        print_faults_follow_up!(save_game_faults, "Save Game");
-        print_faults_follow_up!(performance_faults, "Performance");
-        print_faults_follow_up!(stability_faults, "Stability");
-        print_faults!(significant_bugs, "Significant Bugs");
-
         if let Some(concluding_notes) = &report.responses.concluding_notes {
             if !concluding_notes.is_empty() {
                 label!();
@@ -543,8 +825,133 @@ pub(crate) async fn display_reports(reports: Reports, args: &Args, client: &Clie
             }
         }
 
+        if args.verbose {
+            for (section, fields) in report_details(report) {
+                label!();
+                label!(section.bold());
+                for (name, value) in fields {
+                    label!(format!("  {}", name), value);
+                }
+            }
+        }
+
         if args.images && IMAGE_HEIGHT > lines_printed {
             print!("{}", "\n".repeat((IMAGE_HEIGHT - lines_printed) as usize));
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(fixture: &str) -> Vec<String> {
+        colored::control::set_override(false);
+        fault_lines(&serde_json::from_str(fixture).unwrap())
+    }
+
+    #[test]
+    fn faults_with_follow_up_and_notes() {
+        assert_eq!(lines(include_str!("../tests/fixtures/responses/follow_up_and_notes.json")), [
+            "", "Audio: Low Quality", "Crackling in cutscenes",
+            "", "Significant Bugs: Yes", "Crashes when loading chapter 3",
+        ]);
+    }
+
+    #[test]
+    fn faults_with_follow_up_only() {
+        assert_eq!(lines(include_str!("../tests/fixtures/responses/follow_up_only.json")), [
+            "", "Audio: Low Quality",
+            "", "Significant Bugs: Yes", "details not provided",
+        ]);
+    }
+
+    #[test]
+    fn faults_with_notes_only() {
+        assert_eq!(lines(include_str!("../tests/fixtures/responses/notes_only.json")), [
+            "", "Audio: details not provided", "Crackling in cutscenes",
+            "", "Significant Bugs: Yes", "Crashes when loading chapter 3",
+        ]);
+    }
+
+    #[test]
+    fn faults_without_follow_up_or_notes() {
+        assert_eq!(lines(include_str!("../tests/fixtures/responses/neither.json")), [
+            "", "Audio: details not provided",
+            "", "Significant Bugs: Yes", "details not provided",
+        ]);
+    }
+
+    #[test]
+    fn faults_with_follow_up_for_other_categories() {
+        assert_eq!(lines(include_str!("../tests/fixtures/responses/follow_up_without_category.json")), [
+            "", "Audio: details not provided", "Crackling in cutscenes",
+            "", "Graphics: Minor Artifacts",
+            "", "Significant Bugs: Yes", "Crashes when loading chapter 3",
+        ]);
+    }
+
+    #[test]
+    fn faults_with_every_follow_up_flag_false() {
+        assert_eq!(lines(include_str!("../tests/fixtures/responses/follow_up_all_false.json")), [
+            "", "Audio: details not provided",
+            "", "Windowing: details not provided", "Alt-tab leaves a black screen",
+            "", "Significant Bugs: Yes", "Crashes when loading chapter 3",
+        ]);
+    }
+
+    #[test]
+    fn faults_with_empty_notes() {
+        assert_eq!(lines(include_str!("../tests/fixtures/responses/empty_notes.json")), [
+            "", "Audio: Low Quality",
+            "", "Graphics: details not provided",
+            "", "Significant Bugs: Yes", "details not provided",
+        ]);
+    }
+
+    fn altered_reports() -> String {
+        let mut value: serde_json::Value = serde_json::from_str(include_str!("../tests/fixtures/reports.json")).unwrap();
+        value["reports"][0]["unexpected"] = serde_json::json!(true);
+        value["reports"][1]["responses"] = serde_json::json!(3);
+        value.to_string()
+    }
+
+    #[test]
+    fn skips_unreadable_reports() {
+        let reports = parse_reports(&altered_reports(), false).unwrap();
+        assert_eq!((reports.reports.len(), reports.decoded, reports.skipped), (11, 11, 1));
+        assert_eq!(reports.schema_issues.len(), 1);
+        assert!(reports.schema_issues[0].starts_with("skipped report"));
+    }
+
+    #[test]
+    fn compares_schema_only_when_asked() {
+        let reports = parse_reports(&altered_reports(), true).unwrap();
+        assert!(reports.schema_issues.contains(&"unknown field unexpected".to_string()));
+        assert_eq!(reports.schema_issues.len(), 2);
+    }
+
+    #[test]
+    fn stops_at_the_last_page() {
+        let page = parse_reports(include_str!("../tests/fixtures/reports.json"), false).unwrap();
+        // 12 of 30 reports per page
+        assert!(!is_last_page(&page, 12));
+        assert!(!is_last_page(&page, 24));
+        assert!(is_last_page(&page, 36));
+        assert!(is_last_page(&Reports { total: 30, ..Default::default() }, 24));
+    }
+
+    #[test]
+    fn appends_pages() {
+        let mut reports = parse_reports(&altered_reports(), true).unwrap();
+        reports.append(parse_reports(include_str!("../tests/fixtures/reports.json"), true).unwrap());
+        assert_eq!((reports.reports.len(), reports.decoded, reports.skipped), (23, 23, 1));
+        assert_eq!((reports.page, reports.total), (1, 30));
+        assert_eq!(reports.schema_issues.len(), 2);
+    }
+
+    #[test]
+    fn rejects_unexpected_files() {
+        assert!(parse_reports("[]", false).is_none());
+        assert!(parse_reports("<html></html>", false).is_none());
+    }
+}
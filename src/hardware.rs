@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::Path;
+use crate::display_reports::Specs;
+
+// minimum number of matching components for a report to count as coming from a similar system
+pub(crate) const SIMILAR_SYSTEM_THRESHOLD: usize = 3;
+
+fn read_file(root: &Path, path: &str) -> Option<String> {
+    fs::read_to_string(root.join(path)).ok()
+}
+
+fn detect_cpu(root: &Path) -> Option<String> {
+    let cpuinfo = read_file(root, "proc/cpuinfo")?;
+    cpuinfo.lines()
+        .find(|line| line.starts_with("model name"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, value)| value.trim().to_string())
+}
+
+fn detect_ram(root: &Path) -> Option<String> {
+    let meminfo = read_file(root, "proc/meminfo")?;
+    let kilobytes: u64 = meminfo.lines()
+        .find(|line| line.starts_with("MemTotal:"))?
+        .split_whitespace()
+        .nth(1)?
+        .parse().ok()?;
+    Some(format!("{} GB", (kilobytes as f64 / 1024.0 / 1024.0).round()))
+}
+
+fn detect_os(root: &Path) -> Option<String> {
+    let os_release = read_file(root, "etc/os-release")?;
+    let value = |key: &str| os_release.lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+        .map(|value| value.trim_matches('"').to_string());
+    value("PRETTY_NAME").or_else(|| value("NAME"))
+}
+
+fn detect_kernel(root: &Path) -> Option<String> {
+    read_file(root, "proc/sys/kernel/osrelease").map(|release| release.trim().to_string())
+}
+
+fn detect_gpu(root: &Path) -> Option<(String, String)> {
+    let mut cards: Vec<_> = fs::read_dir(root.join("sys/class/drm")).ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.strip_prefix("card").is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(|entry| entry.path().join("device"))
+        .collect();
+    cards.sort();
+
+    // prefer a discrete card when both an integrated and a discrete one are present
+    let vendor_of = |device: &Path| fs::read_to_string(device.join("vendor")).ok().map(|v| v.trim().to_lowercase());
+    let device = cards.iter().find(|device| vendor_of(device).is_some_and(|v| v != "0x8086"))
+        .or(cards.first())?;
+
+    let vendor = match vendor_of(device)?.as_str() {
+        "0x10de" => "NVIDIA",
+        "0x1002" => "AMD",
+        "0x8086" => "Intel",
+        _ => "Unknown",
+    };
+    let device_id = fs::read_to_string(device.join("device")).map(|id| id.trim().to_string()).unwrap_or_default();
+    let gpu = format!("{} {}", vendor, device_id).trim().to_string();
+
+    let kernel_driver = fs::read_link(device.join("driver")).ok()
+        .and_then(|driver| driver.file_name().map(|name| name.to_string_lossy().to_string()))
+        .unwrap_or_default();
+    let gpu_driver = if kernel_driver == "nvidia" {
+        let version = read_file(root, "sys/module/nvidia/version").map(|v| v.trim().to_string()).unwrap_or_default();
+        format!("NVIDIA {}", version).trim().to_string()
+    } else {
+        format!("Mesa ({})", kernel_driver)
+    };
+
+    Some((gpu, gpu_driver))
+}
+
+pub(crate) fn detect_specs(root: &Path) -> Specs {
+    let (gpu, gpu_driver) = detect_gpu(root).unwrap_or_default();
+    Specs {
+        cpu: detect_cpu(root).unwrap_or_default(),
+        gpu,
+        gpu_driver,
+        kernel: detect_kernel(root).unwrap_or_default(),
+        os: detect_os(root).unwrap_or_default(),
+        ram: detect_ram(root).unwrap_or_default(),
+        steam_runtime_version: None,
+        x_window_manager: None,
+    }
+}
+
+pub(crate) fn is_steam_deck(root: &Path) -> bool {
+    let board_vendor = read_file(root, "sys/class/dmi/id/board_vendor").unwrap_or_default();
+    let board_name = read_file(root, "sys/class/dmi/id/board_name").unwrap_or_default();
+    board_vendor.trim() == "Valve" && matches!(board_name.trim(), "Jupiter" | "Galileo")
+}
+
+pub(crate) fn gpu_vendor(gpu: &str) -> &'static str {
+    let gpu = gpu.to_lowercase();
+    if gpu.contains("nvidia") || gpu.contains("geforce") {
+        "NVIDIA"
+    } else if gpu.contains("amd") || gpu.contains("radeon") {
+        "AMD"
+    } else if gpu.contains("intel") {
+        "Intel"
+    } else {
+        "Other"
+    }
+}
+
+fn cpu_vendor(cpu: &str) -> &'static str {
+    let cpu = cpu.to_lowercase();
+    if cpu.contains("amd") {
+        "AMD"
+    } else if cpu.contains("intel") {
+        "Intel"
+    } else {
+        "Other"
+    }
+}
+
+fn driver_family(gpu_driver: &str) -> &'static str {
+    let gpu_driver = gpu_driver.to_lowercase();
+    if gpu_driver.contains("nvidia") {
+        "NVIDIA"
+    } else if gpu_driver.contains("mesa") {
+        "Mesa"
+    } else {
+        "Other"
+    }
+}
+
+fn distro(os: &str) -> String {
+    os.split_whitespace().next().unwrap_or_default().to_lowercase()
+}
+
+fn kernel_series(kernel: &str) -> String {
+    kernel.split('.').take(2).collect::<Vec<_>>().join(".")
+}
+
+pub(crate) fn matching_specs(local: &Specs, other: &Specs) -> Vec<&'static str> {
+    let mut matches = Vec::new();
+    let same = |a: &str, b: &str| !a.is_empty() && a != "Other" && a == b;
+    if same(gpu_vendor(&local.gpu), gpu_vendor(&other.gpu)) { matches.push("GPU") }
+    if same(driver_family(&local.gpu_driver), driver_family(&other.gpu_driver)) { matches.push("Driver") }
+    if same(cpu_vendor(&local.cpu), cpu_vendor(&other.cpu)) { matches.push("CPU") }
+    if same(&kernel_series(&local.kernel), &kernel_series(&other.kernel)) { matches.push("Kernel") }
+    if same(&distro(&local.os), &distro(&other.os)) { matches.push("OS") }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use crate::utils::test_dir;
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn add_card(root: &Path, card: &str, vendor: &str, device: &str, driver: &str) {
+        write(root, &format!("sys/class/drm/{}/device/vendor", card), &format!("{}\n", vendor));
+        write(root, &format!("sys/class/drm/{}/device/device", card), &format!("{}\n", device));
+        fs::create_dir_all(root.join("sys/bus/pci/drivers").join(driver)).unwrap();
+        std::os::unix::fs::symlink(root.join("sys/bus/pci/drivers").join(driver), root.join("sys/class/drm").join(card).join("device/driver")).unwrap();
+    }
+
+    // a desktop with an Intel iGPU next to an NVIDIA card
+    fn desktop(name: &str) -> PathBuf {
+        let root = test_dir(name);
+        write(&root, "proc/cpuinfo", "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Core(TM) i7-10700K CPU @ 3.80GHz\n\nprocessor\t: 1\nmodel name\t: Intel(R) Core(TM) i7-10700K CPU @ 3.80GHz\n");
+        write(&root, "proc/meminfo", "MemTotal:       32768000 kB\nMemFree:         1024000 kB\n");
+        write(&root, "proc/sys/kernel/osrelease", "6.9.7-arch1-1\n");
+        write(&root, "etc/os-release", "NAME=\"Arch Linux\"\nPRETTY_NAME=\"Arch Linux\"\nID=arch\n");
+        write(&root, "sys/module/nvidia/version", "550.90.07\n");
+        add_card(&root, "card0", "0x8086", "0x9bc5", "i915");
+        add_card(&root, "card1", "0x10de", "0x2484", "nvidia");
+        // connectors are listed next to the cards and aren't cards themselves
+        fs::create_dir_all(root.join("sys/class/drm/card1-DP-1")).unwrap();
+        root
+    }
+
+    #[test]
+    fn detects_desktop_specs() {
+        let specs = detect_specs(&desktop("desktop"));
+        assert_eq!(specs.cpu, "Intel(R) Core(TM) i7-10700K CPU @ 3.80GHz");
+        assert_eq!(specs.ram, "31 GB");
+        assert_eq!(specs.kernel, "6.9.7-arch1-1");
+        assert_eq!(specs.os, "Arch Linux");
+        assert_eq!(specs.gpu, "NVIDIA 0x2484");
+        assert_eq!(specs.gpu_driver, "NVIDIA 550.90.07");
+    }
+
+    #[test]
+    fn detects_steam_deck_specs() {
+        let root = test_dir("steam-deck");
+        write(&root, "proc/cpuinfo", "model name\t: AMD Custom APU 0405\n");
+        write(&root, "proc/meminfo", "MemTotal:       16000000 kB\n");
+        write(&root, "etc/os-release", "NAME=\"SteamOS\"\nID=steamos\n");
+        write(&root, "sys/class/dmi/id/board_vendor", "Valve\n");
+        write(&root, "sys/class/dmi/id/board_name", "Jupiter\n");
+        add_card(&root, "card0", "0x1002", "0x163f", "amdgpu");
+
+        let specs = detect_specs(&root);
+        assert_eq!(specs.cpu, "AMD Custom APU 0405");
+        assert_eq!(specs.ram, "15 GB");
+        assert_eq!(specs.os, "SteamOS");
+        assert_eq!(specs.kernel, "");
+        assert_eq!((specs.gpu.as_str(), specs.gpu_driver.as_str()), ("AMD 0x163f", "Mesa (amdgpu)"));
+        assert!(is_steam_deck(&root));
+        assert!(!is_steam_deck(&desktop("not-a-deck")));
+    }
+
+    #[test]
+    fn leaves_missing_specs_empty() {
+        let specs = detect_specs(&test_dir("empty-root"));
+        assert!([specs.cpu, specs.gpu, specs.gpu_driver, specs.kernel, specs.os, specs.ram].iter().all(String::is_empty));
+    }
+
+    #[test]
+    fn matches_similar_systems() {
+        let local = detect_specs(&desktop("similar"));
+        let mut other = local.clone();
+        other.cpu = "AMD Ryzen 7 5800X".to_string();
+        other.gpu = "NVIDIA GeForce RTX 3070".to_string();
+        other.kernel = "6.9.3".to_string();
+        other.os = "Arch Linux (64 bit)".to_string();
+        assert_eq!(matching_specs(&local, &other), ["GPU", "Driver", "Kernel", "OS"]);
+
+        // unknown vendors never count as a match
+        let unknown = Specs { cpu: "?".to_string(), gpu: "?".to_string(), gpu_driver: "?".to_string(), ..other.clone() };
+        assert_eq!(matching_specs(&unknown, &unknown), ["Kernel", "OS"]);
+    }
+}
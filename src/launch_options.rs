@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use colored::Colorize;
+use crate::display_reports::Report;
+use crate::stats::{percentage, print_heading};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, strum_macros::Display)]
+pub(crate) enum TokenKind {
+    #[strum(serialize = "env var")]
+    EnvVar,
+    #[strum(serialize = "command")]
+    Command,
+    #[strum(serialize = "wrapper")]
+    Wrapper,
+    #[strum(serialize = "flag")]
+    Flag,
+    #[strum(serialize = "argument")]
+    Argument,
+}
+
+pub(crate) struct Token {
+    pub text: String,
+    pub kind: TokenKind,
+}
+
+pub(crate) struct LaunchOptionUsage {
+    pub token: String,
+    pub kind: TokenKind,
+    pub count: usize,
+    pub works: usize,
+}
+
+fn split_words(launch_options: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quote = None;
+    for char in launch_options.chars() {
+        match (quote, char) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(char),
+            (None, c) if c.is_whitespace() => {
+                if !word.is_empty() { words.push(std::mem::take(&mut word)) }
+            }
+            (_, c) => word.push(c),
+        }
+    }
+    if !word.is_empty() { words.push(word) }
+    words
+}
+
+fn is_env_var(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(key, _)| {
+        !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') && !key.starts_with(|c: char| c.is_ascii_digit())
+    })
+}
+
+pub(crate) fn tokenize(launch_options: &str) -> Vec<Token> {
+    let mut after_command = false;
+    // words before %command% are wrapper programs (gamemoderun mangohud, gamescope, ...) unless they are the value of a flag
+    let mut after_flag = false;
+    split_words(launch_options).into_iter()
+        .map(|text| {
+            let kind = if text == "%command%" {
+                after_command = true;
+                TokenKind::Command
+            } else if !after_command && is_env_var(&text) {
+                after_flag = false;
+                TokenKind::EnvVar
+            } else if text.starts_with('-') || text.starts_with('+') {
+                // "--" ends a wrapper's own flags and "--flag=value" carries its value
+                after_flag = text != "--" && !text.contains('=');
+                TokenKind::Flag
+            } else if !after_command && !after_flag {
+                TokenKind::Wrapper
+            } else {
+                after_flag = false;
+                TokenKind::Argument
+            };
+            Token { text, kind }
+        })
+        .collect()
+}
+
+pub(crate) fn launch_option_usage(reports: &[Report]) -> Vec<LaunchOptionUsage> {
+    let mut usage: HashMap<(String, TokenKind), (usize, usize)> = HashMap::new();
+    for report in reports {
+        let Some(launch_options) = &report.responses.launch_options else { continue };
+        let works = report.outcome().works();
+
+        // count each token once per report
+        let mut tokens = tokenize(launch_options);
+        tokens.sort_by(|a, b| a.text.cmp(&b.text));
+        tokens.dedup_by(|a, b| a.text == b.text);
+        for token in tokens {
+            let entry = usage.entry((token.text, token.kind)).or_insert((0, 0));
+            entry.0 += 1;
+            if works { entry.1 += 1 }
+        }
+    }
+
+    let mut usage: Vec<_> = usage.into_iter()
+        .map(|((token, kind), (count, works))| LaunchOptionUsage { token, kind, count, works })
+        .collect();
+    usage.sort_by(|a, b| b.count.cmp(&a.count).then(b.works.cmp(&a.works)).then(a.token.cmp(&b.token)));
+    usage
+}
+
+pub(crate) fn display_launch_options(reports: &[Report], terminal_width: u16) {
+    const TOP_COUNT: usize = 15;
+
+    println!("{}", "—".repeat(terminal_width as usize));
+
+    let with_launch_options = reports.iter().filter(|report| report.responses.launch_options.is_some()).count();
+    println!("{} {} of {} reports",
+        "Launch Options:".truecolor(200, 200, 200),
+        with_launch_options,
+        reports.len()
+    );
+
+    // every launch option string contains %command%, so it says nothing on its own
+    let usage: Vec<_> = launch_option_usage(reports).into_iter()
+        .filter(|usage| usage.kind != TokenKind::Command)
+        .collect();
+    if usage.is_empty() {
+        return;
+    }
+
+    print_heading("Most Common Tokens");
+    let width = usage.iter().take(TOP_COUNT).map(|usage| usage.token.chars().count()).max().unwrap_or(0);
+    for usage in usage.iter().take(TOP_COUNT) {
+        let works = format!("{} works ({}%)", usage.works, percentage(usage.works, usage.count));
+        println!("  {}{}  {:<8}  {:>3} {}  {}",
+            usage.token.on_truecolor(68, 68, 68),
+            " ".repeat(width - usage.token.chars().count()),
+            usage.kind.to_string().truecolor(120, 120, 120),
+            usage.count,
+            if usage.count == 1 { "report " } else { "reports" },
+            if usage.works * 2 >= usage.count { works.green() } else { works.yellow() }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(launch_options: &str) -> Vec<(String, String)> {
+        tokenize(launch_options).into_iter().map(|token| (token.text, token.kind.to_string())).collect()
+    }
+
+    fn expected(tokens: &[(&str, &str)]) -> Vec<(String, String)> {
+        tokens.iter().map(|(text, kind)| (text.to_string(), kind.to_string())).collect()
+    }
+
+    #[test]
+    fn reads_env_vars() {
+        assert_eq!(tokens("PROTON_USE_WINED3D=1 DXVK_ASYNC=1 %command%"), expected(&[
+            ("PROTON_USE_WINED3D=1", "env var"), ("DXVK_ASYNC=1", "env var"), ("%command%", "command"),
+        ]));
+        // after %command% everything belongs to the game
+        assert_eq!(tokens("%command% SKIP=1"), expected(&[("%command%", "command"), ("SKIP=1", "argument")]));
+        assert_eq!(tokens("1FOO=1 %command%"), expected(&[("1FOO=1", "wrapper"), ("%command%", "command")]));
+    }
+
+    #[test]
+    fn reads_chained_wrappers() {
+        assert_eq!(tokens("gamemoderun mangohud %command%"), expected(&[
+            ("gamemoderun", "wrapper"), ("mangohud", "wrapper"), ("%command%", "command"),
+        ]));
+        assert_eq!(tokens("MANGOHUD=1 obs-gamecapture gamemoderun %command% -skipintro"), expected(&[
+            ("MANGOHUD=1", "env var"), ("obs-gamecapture", "wrapper"), ("gamemoderun", "wrapper"),
+            ("%command%", "command"), ("-skipintro", "flag"),
+        ]));
+    }
+
+    #[test]
+    fn reads_gamescope_flags() {
+        assert_eq!(tokens("gamescope -W 2560 -H 1440 -f --force-grab-cursor -- gamemoderun %command% +fps_max 144"), expected(&[
+            ("gamescope", "wrapper"), ("-W", "flag"), ("2560", "argument"), ("-H", "flag"), ("1440", "argument"),
+            ("-f", "flag"), ("--force-grab-cursor", "flag"), ("--", "flag"), ("gamemoderun", "wrapper"),
+            ("%command%", "command"), ("+fps_max", "flag"), ("144", "argument"),
+        ]));
+        assert_eq!(tokens("gamescope --output-width=1920 mangohud %command%"), expected(&[
+            ("gamescope", "wrapper"), ("--output-width=1920", "flag"), ("mangohud", "wrapper"), ("%command%", "command"),
+        ]));
+    }
+
+    #[test]
+    fn keeps_quoted_values_together() {
+        assert_eq!(tokens(r#"WINEDLLOVERRIDES="dinput8,dxgi=n,b" %command% -launcher 'C:\Program Files\game.exe'"#), expected(&[
+            ("WINEDLLOVERRIDES=dinput8,dxgi=n,b", "env var"), ("%command%", "command"),
+            ("-launcher", "flag"), ("C:\\Program Files\\game.exe", "argument"),
+        ]));
+        assert_eq!(tokens(r#"PROTON_LOG_DIR="/home/user/proton logs" %command%"#), expected(&[
+            ("PROTON_LOG_DIR=/home/user/proton logs", "env var"), ("%command%", "command"),
+        ]));
+    }
+}
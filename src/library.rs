@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use colored::Colorize;
+use futures::{join, stream, StreamExt};
+use reqwest::Client;
+use crate::args::Args;
+use crate::compat_tools::{compat_tool_mapping, same_proton, tool_display_name};
+use crate::display_reports::{fetch_counts, fetch_reports_with_counts, Report};
+use crate::recommend::recommend_proton;
+use crate::post_result::Game;
+use crate::tier::{DeckStatus, Tier};
+use crate::utils::{fetch_summaries, get_colored_tier, CONCURRENT_REQUESTS, is_native, resolve_game, visible_width, Summary};
+use crate::vdf;
+
+// apps installed through Steam that are runtimes and compatibility tools rather than games
+const TOOL_NAME_PREFIXES: [&str; 4] = ["Proton", "Steam Linux Runtime", "Steamworks Common Redistributables", "SteamVR"];
+
+pub(crate) struct InstalledApp {
+    pub app_id: u32,
+    pub name: String,
+}
+
+struct LibraryRow<'a> {
+    game: &'a InstalledApp,
+    tier: Tier,
+    oslist: Vec<String>,
+    // the configured tool and whether it is Steam Play's default rather than set for this game
+    configured_tool: Option<(String, bool)>,
+    best_proton: Option<String>,
+}
+
+pub(crate) fn find_steam_roots(home: &Path) -> Vec<PathBuf> {
+    let candidates = [
+        home.join(".steam/steam"),
+        home.join(".steam/root"),
+        home.join(".local/share/Steam"),
+        home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"),
+    ];
+
+    // ~/.steam/steam is usually a symlink to one of the other locations
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for candidate in candidates {
+        if !candidate.join("steamapps").is_dir() { continue }
+        let canonical = candidate.canonicalize().unwrap_or(candidate);
+        if !roots.contains(&canonical) {
+            roots.push(canonical);
+        }
+    }
+    roots
+}
+
+pub(crate) fn steam_roots(args: &Args) -> Vec<PathBuf> {
+    match &args.steam_dir {
+        Some(steam_dir) => vec![steam_dir.clone()],
+        None => find_steam_roots(&PathBuf::from(std::env::var("HOME").unwrap_or_default())),
+    }
+}
+
+pub(crate) fn library_folders(steam_root: &Path) -> Vec<PathBuf> {
+    let mut folders = vec![steam_root.to_path_buf()];
+
+    let Ok(text) = fs::read_to_string(steam_root.join("steamapps/libraryfolders.vdf")) else { return folders };
+    let Ok(library_folders) = vdf::parse(&text) else { return folders };
+    let entries = library_folders.get("libraryfolders").map(|value| value.entries()).unwrap_or_default();
+
+    for (_, folder) in entries {
+        // older Steam versions store the path directly instead of an object with a "path" key
+        let path = folder.get("path").and_then(|path| path.as_str()).or(folder.as_str());
+        if let Some(path) = path {
+            let path = PathBuf::from(path);
+            if !folders.contains(&path) && !path.canonicalize().is_ok_and(|path| folders.contains(&path)) {
+                folders.push(path);
+            }
+        }
+    }
+    folders
+}
+
+pub(crate) fn installed_apps(library: &Path) -> Vec<InstalledApp> {
+    let Ok(entries) = fs::read_dir(library.join("steamapps")) else { return Vec::new() };
+
+    let mut apps: Vec<InstalledApp> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with("appmanifest_") && name.ends_with(".acf")
+        })
+        .filter_map(|entry| {
+            let manifest = vdf::parse(&fs::read_to_string(entry.path()).ok()?).ok()?;
+            let app_state = manifest.get("AppState")?;
+            Some(InstalledApp {
+                app_id: app_state.get("appid")?.as_str()?.parse().ok()?,
+                name: app_state.get("name").and_then(|name| name.as_str()).unwrap_or_default().to_string(),
+            })
+        })
+        .collect();
+    apps.sort_by_key(|app| app.app_id);
+    apps
+}
+
+// app types ("Game", "Tool", "Application", ...) from Steam's appinfo cache
+pub(crate) fn app_types(steam_root: &Path) -> HashMap<u32, String> {
+    let Ok(bytes) = fs::read(steam_root.join("appcache/appinfo.vdf")) else { return HashMap::new() };
+    let Ok(apps) = vdf::parse_appinfo(&bytes) else { return HashMap::new() };
+    apps.into_iter()
+        .filter_map(|app| {
+            let app_type = app.data.get("appinfo")?.get("common")?.get("type")?.as_str()?.to_string();
+            Some((app.app_id, app_type))
+        })
+        .collect()
+}
+
+pub(crate) fn is_tool(app: &InstalledApp, app_types: &HashMap<u32, String>) -> bool {
+    if let Some(app_type) = app_types.get(&app.app_id) {
+        return !app_type.eq_ignore_ascii_case("game");
+    }
+    app.app_id == 228980 || TOOL_NAME_PREFIXES.iter().any(|prefix| app.name.starts_with(prefix))
+}
+
+pub(crate) fn installed_games(steam_roots: &[PathBuf]) -> Vec<InstalledApp> {
+    let mut games: Vec<InstalledApp> = Vec::new();
+    for steam_root in steam_roots {
+        let app_types = app_types(steam_root);
+        for library in library_folders(steam_root) {
+            for app in installed_apps(&library) {
+                if !is_tool(&app, &app_types) && !games.iter().any(|game| game.app_id == app.app_id) {
+                    games.push(app);
+                }
+            }
+        }
+    }
+    games
+}
+
+// summaries plus the search hits carrying each game's oslist (native support and Steam Deck status)
+pub(crate) async fn fetch_ratings(client: &Client, games: &[InstalledApp]) -> (Vec<Option<Summary>>, Vec<Option<Game>>) {
+    stream::iter(games)
+        .map(|game| async move {
+            let steam_id = game.app_id.to_string();
+            let (summaries, hit) = join!(fetch_summaries(client, std::slice::from_ref(&steam_id)), resolve_game(client, &steam_id));
+            (summaries.into_iter().next().flatten(), hit)
+        })
+        .buffered(CONCURRENT_REQUESTS)
+        .unzip()
+        .await
+}
+
+pub(crate) async fn display_library(args: &Args, client: &Client) {
+    let steam_roots = steam_roots(args);
+    if steam_roots.is_empty() {
+        println!("No Steam installation found (use --steam-dir to point to one)");
+        return;
+    }
+
+    let games = installed_games(&steam_roots);
+    if games.is_empty() {
+        println!("No installed games found");
+        return;
+    }
+
+    // every game's reports are needed for its best Proton version, which adds up to a lot of downloads on a big library
+    let reports = async {
+        let counts = fetch_counts(client).await;
+        stream::iter(&games)
+            .map(|game| fetch_reports_with_counts(client, game.app_id, &counts))
+            .buffered(CONCURRENT_REQUESTS)
+            .collect::<Vec<_>>()
+            .await
+    };
+    let ((summaries, hits), reports) = join!(fetch_ratings(client, &games), reports);
+
+    let mut compat_tools = HashMap::new();
+    for steam_root in steam_roots.iter().rev() {
+        compat_tools.extend(compat_tool_mapping(steam_root));
+    }
+    let device = args.device_filter();
+
+    let mut rows: Vec<LibraryRow> = games.iter().zip(summaries).zip(hits).zip(reports)
+        .map(|(((game, summary), hit), reports)| {
+            let best_proton = reports.and_then(|reports| {
+                let reports: Vec<Report> = reports.reports.into_iter().filter(|report| device.matches(&report.device)).collect();
+                recommend_proton(&reports).into_iter().next().map(|choice| choice.name)
+            });
+            let configured_tool = match compat_tools.get(&game.app_id.to_string()) {
+                Some(tool) => Some((tool.clone(), false)),
+                None => compat_tools.get("0").map(|tool| (tool.clone(), true)),
+            };
+            LibraryRow {
+                game,
+                tier: summary.map(|summary| summary.tier).unwrap_or(Tier::Pending),
+                oslist: hit.map(|hit| hit.oslist).unwrap_or_default(),
+                configured_tool,
+                best_proton,
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.tier.cmp(&a.tier).then(a.game.name.to_lowercase().cmp(&b.game.name.to_lowercase()))
+    });
+
+    let ratings: Vec<String> = rows.iter().map(|row| get_colored_tier(&row.tier, &row.oslist, args.prefer)).collect();
+    let rating_width = ratings.iter().map(|rating| visible_width(rating)).max().unwrap_or(0).max("Rating".len());
+    let name_width = rows.iter().map(|row| row.game.name.chars().count()).max().unwrap_or(0);
+    let tool_width = rows.iter()
+        .filter_map(|row| row.configured_tool.as_ref())
+        .map(|(tool, default)| tool_display_name(tool).chars().count() + if *default { 10 } else { 0 })
+        .max().unwrap_or(0).max("Configured".len());
+    println!("{}",
+        format!("{:<name_width$}  {:<10}  {:<rating_width$}  {:<6}  {:<11}  {:<tool_width$}  {}", "Name", "App ID", "Rating", "Native", "Steam Deck", "Configured", "Best (reports)").truecolor(200, 200, 200)
+    );
+    for (row, rating) in rows.into_iter().zip(ratings) {
+        let steam_deck_status = DeckStatus::from_oslist(&row.oslist)
+            .map(|status| status.colored())
+            .unwrap_or("unknown".truecolor(120, 120, 120));
+
+        let mismatch = match (&row.configured_tool, &row.best_proton) {
+            (Some((tool, _)), Some(best)) => !same_proton(tool, best),
+            _ => false,
+        };
+        let configured_tool = match &row.configured_tool {
+            Some((tool, false)) => tool_display_name(tool).normal(),
+            Some((tool, true)) => format!("{} (default)", tool_display_name(tool)).truecolor(120, 120, 120),
+            None => "-".truecolor(120, 120, 120),
+        };
+        let best_proton = match &row.best_proton {
+            Some(best) if mismatch => format!("{} (mismatch)", best).yellow(),
+            Some(best) => best.normal(),
+            None => "-".truecolor(120, 120, 120),
+        };
+
+        // whether there is a native Linux version, in its own column so it doesn't depend on --prefer
+        let native = if is_native(&row.oslist) { "yes".green() } else { "no".truecolor(120, 120, 120) };
+
+        // colored padding counts the escape codes of the combined native and Proton rating
+        println!("{:<name_width$}  {:<10}  {}{}  {:<6}  {:<11}  {:<tool_width$}  {}",
+            row.game.name.bold(),
+            row.game.app_id,
+            rating,
+            " ".repeat(rating_width - visible_width(&rating)),
+            native,
+            steam_deck_status,
+            configured_tool,
+            best_proton
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = "tests/fixtures/steam";
+
+    #[test]
+    fn finds_every_library_folder() {
+        assert_eq!(library_folders(Path::new(FIXTURE)), [
+            PathBuf::from(FIXTURE),
+            PathBuf::from("tests/fixtures/steam-library"),
+            PathBuf::from("tests/fixtures/unmounted-drive"),
+        ]);
+        // a Steam directory without libraryfolders.vdf is its own only library
+        assert_eq!(library_folders(Path::new("tests/fixtures/steam-library")), [PathBuf::from("tests/fixtures/steam-library")]);
+    }
+
+    #[test]
+    fn reads_app_manifests() {
+        let apps = installed_apps(Path::new(FIXTURE));
+        let apps: Vec<(u32, &str)> = apps.iter().map(|app| (app.app_id, app.name.as_str())).collect();
+        assert_eq!(apps, [(1091500, "Cyberpunk 2077"), (1493710, "Proton Experimental"), (1628350, "Steam Linux Runtime 3.0 (sniper)")]);
+        assert!(installed_apps(Path::new("tests/fixtures/unmounted-drive")).is_empty());
+    }
+
+    #[test]
+    fn reads_app_types_from_appinfo() {
+        let app_types = app_types(Path::new(FIXTURE));
+        assert_eq!(app_types.get(&1091500).map(String::as_str), Some("Game"));
+        assert_eq!(app_types.get(&1493710).map(String::as_str), Some("Tool"));
+        assert_eq!(app_types.get(&1628350), None);
+    }
+
+    #[test]
+    fn lists_installed_games_once() {
+        let games = installed_games(&[PathBuf::from(FIXTURE)]);
+        let games: Vec<(u32, &str)> = games.iter().map(|game| (game.app_id, game.name.as_str())).collect();
+        // Proton is a tool in appinfo.vdf and the runtime, missing from it, is recognized by name
+        assert_eq!(games, [(1091500, "Cyberpunk 2077"), (292030, "The Witcher 3: Wild Hunt")]);
+    }
+
+    #[test]
+    fn finds_steam_roots_in_home() {
+        let home = crate::utils::test_dir("home");
+        assert!(find_steam_roots(&home).is_empty());
+        fs::create_dir_all(home.join(".local/share/Steam/steamapps")).unwrap();
+        // ~/.steam/steam pointing to the same installation is only listed once
+        fs::create_dir_all(home.join(".steam")).unwrap();
+        std::os::unix::fs::symlink(home.join(".local/share/Steam"), home.join(".steam/steam")).unwrap();
+        assert_eq!(find_steam_roots(&home), [home.join(".local/share/Steam").canonicalize().unwrap()]);
+    }
+}
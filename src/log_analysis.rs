@@ -0,0 +1,295 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono_humanize::HumanTime;
+use colored::Colorize;
+use reqwest::Client;
+use crate::args::Args;
+use crate::display_reports::{fetch_reports, CustomizationsUsed, Report};
+use crate::recommend::proton_name;
+use crate::stats::print_heading;
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum ProblemKind {
+    MissingDll,
+    Vkd3d,
+    AntiCheat,
+    MediaFoundation,
+}
+
+pub(crate) struct Problem {
+    pub kind: ProblemKind,
+    pub title: String,
+    // lowercase words that reports mention when they work around the problem
+    pub keywords: Vec<String>,
+    pub lines: Vec<String>,
+}
+
+pub(crate) struct Match<'a> {
+    pub report: &'a Report,
+    pub note: Option<String>,
+    pub customizations: Vec<&'static str>,
+}
+
+const VKD3D_KEYWORDS: [&str; 4] = ["vkd3d", "dx12", "d3d12", "directx 12"];
+const ANTI_CHEAT_KEYWORDS: [&str; 4] = ["easyanticheat", "easy anti-cheat", "anti-cheat", "anticheat"];
+const MEDIA_FOUNDATION_KEYWORDS: [&str; 4] = ["media foundation", "mfplat", "cutscene", "video"];
+
+// DLL name prefixes and the winetricks verbs that install them
+const DLL_VERBS: [(&str, &str); 6] = [
+    ("msvcp", "vcrun"), ("vcruntime", "vcrun"), ("msvcr", "vcrun"),
+    ("d3dx9", "d3dx9"), ("xaudio", "xact"), ("xinput", "xinput"),
+];
+
+// Wine's error lines look like "0024:err:module:import_dll ...", optionally behind a timestamp and process ID;
+// returns what follows the "err:" class
+fn wine_error(line: &str) -> Option<&str> {
+    let mut rest = line.trim_start();
+    loop {
+        let (part, tail) = rest.split_once(':')?;
+        if part == "err" { return Some(tail) }
+        if part.is_empty() || !part.chars().all(|c| c.is_ascii_hexdigit() || c == '.') { return None }
+        rest = tail;
+    }
+}
+
+// "err:module:import_dll Library MSVCP140.dll (which is needed by ...) not found"
+fn missing_dll(line: &str) -> Option<String> {
+    let error = wine_error(line)?.strip_prefix("module:")?;
+    if !error.contains("not found") { return None }
+    let (_, rest) = error.split_once("Library ")?;
+    let dll = rest.split([' ', '(']).next()?.to_lowercase();
+    dll.ends_with(".dll").then_some(dll)
+}
+
+// messages Easy Anti-Cheat shows when it refuses to run, as opposed to it merely being loaded
+const ANTI_CHEAT_FAILURES: [&str; 5] = [
+    "not running with easy anti-cheat", "not installed", "untrusted system file", "virtual machine", "failed to initialize",
+];
+// Wine channels of Media Foundation and the GStreamer backend it plays video with
+const MEDIA_FOUNDATION_CHANNELS: [&str; 4] = ["mf:", "mfplat:", "mfreadwrite:", "winegstreamer:"];
+
+fn problem_kind(line: &str) -> Option<ProblemKind> {
+    let lowercase = line.to_lowercase();
+    let anti_cheat = lowercase.contains("easyanticheat") || lowercase.contains("easy anti-cheat");
+    if anti_cheat && ANTI_CHEAT_FAILURES.iter().any(|failure| lowercase.contains(failure)) {
+        return Some(ProblemKind::AntiCheat);
+    }
+    // vkd3d-proton reports through Wine's channels, named after the function that failed
+    let error = wine_error(&lowercase)?;
+    if error.starts_with("vkd3d") || error.starts_with("d3d12") {
+        Some(ProblemKind::Vkd3d)
+    } else if MEDIA_FOUNDATION_CHANNELS.iter().any(|channel| error.starts_with(channel)) {
+        Some(ProblemKind::MediaFoundation)
+    } else if anti_cheat {
+        Some(ProblemKind::AntiCheat)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn find_problems(log: &str) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = Vec::new();
+    for line in log.lines() {
+        let (kind, title, keywords) = if let Some(dll) = missing_dll(line) {
+            let name = dll.trim_end_matches(".dll").to_string();
+            let mut keywords = vec![dll.clone(), name];
+            keywords.extend(DLL_VERBS.iter().filter(|(prefix, _)| dll.starts_with(prefix)).map(|(_, verb)| verb.to_string()));
+            (ProblemKind::MissingDll, format!("Missing DLL: {}", dll), keywords)
+        } else {
+            match problem_kind(line) {
+                Some(ProblemKind::Vkd3d) => (ProblemKind::Vkd3d, "VKD3D (DirectX 12) errors".to_string(), VKD3D_KEYWORDS.map(String::from).to_vec()),
+                Some(ProblemKind::AntiCheat) => (ProblemKind::AntiCheat, "Easy Anti-Cheat".to_string(), ANTI_CHEAT_KEYWORDS.map(String::from).to_vec()),
+                Some(ProblemKind::MediaFoundation) => (ProblemKind::MediaFoundation, "Media Foundation (video playback)".to_string(), MEDIA_FOUNDATION_KEYWORDS.map(String::from).to_vec()),
+                _ => continue,
+            }
+        };
+
+        match problems.iter_mut().find(|problem| problem.title == title) {
+            Some(problem) => problem.lines.push(line.trim().to_string()),
+            None => problems.push(Problem { kind, title, keywords, lines: vec![line.trim().to_string()] }),
+        }
+    }
+    problems
+}
+
+fn notes(report: &Report) -> Vec<&str> {
+    let responses = &report.responses;
+    let notes = &responses.notes;
+    [
+        &responses.concluding_notes, &notes.verdict, &notes.tinker_override, &notes.launcher, &notes.significant_bugs,
+        &notes.audio_faults, &notes.graphical_faults, &notes.input_faults, &notes.performance_faults,
+        &notes.stability_faults, &notes.windowing_faults, &notes.save_game_faults,
+    ]
+        .into_iter()
+        .filter_map(|note| note.as_deref())
+        .collect()
+}
+
+// the sentence of a note that mentions one of the keywords
+fn matching_note(report: &Report, keywords: &[String]) -> Option<String> {
+    const MAX_LENGTH: usize = 120;
+    notes(report).into_iter()
+        .flat_map(|note| note.split(['.', '\n']))
+        .find(|sentence| {
+            let sentence = sentence.to_lowercase();
+            keywords.iter().any(|keyword| sentence.contains(keyword.as_str()))
+        })
+        .map(|sentence| {
+            let sentence = sentence.trim();
+            if sentence.chars().count() > MAX_LENGTH {
+                format!("{}…", sentence.chars().take(MAX_LENGTH).collect::<String>())
+            } else {
+                sentence.to_string()
+            }
+        })
+}
+
+fn matching_customizations(kind: ProblemKind, customizations: Option<&CustomizationsUsed>) -> Vec<&'static str> {
+    let Some(customizations) = customizations else { return Vec::new() };
+    let mut matches = Vec::new();
+    match kind {
+        ProblemKind::MissingDll => {
+            if customizations.winetricks.unwrap_or(false) { matches.push("winetricks") }
+            if customizations.protontricks.unwrap_or(false) { matches.push("protontricks") }
+        }
+        ProblemKind::MediaFoundation => {
+            if customizations.media_foundation.unwrap_or(false) { matches.push("media foundation") }
+        }
+        ProblemKind::Vkd3d | ProblemKind::AntiCheat => {}
+    }
+    matches
+}
+
+// reports that mention the problem in their notes come first, then working reports, then the newest
+pub(crate) fn matching_reports<'a>(problem: &Problem, reports: &'a [Report]) -> Vec<Match<'a>> {
+    let mut matches: Vec<Match> = reports.iter()
+        .map(|report| Match {
+            report,
+            note: matching_note(report, &problem.keywords),
+            customizations: matching_customizations(problem.kind, report.responses.customizations_used.as_ref()),
+        })
+        .filter(|candidate| candidate.note.is_some() || !candidate.customizations.is_empty())
+        .collect();
+    matches.sort_by_key(|candidate| std::cmp::Reverse((
+        candidate.note.is_some(),
+        candidate.report.outcome().works(),
+        candidate.report.timestamp,
+    )));
+    matches
+}
+
+// "steam-1091500.log" as written by PROTON_LOG=1
+fn app_id_from_path(path: &Path) -> Option<u32> {
+    path.file_name()?.to_str()?.strip_prefix("steam-")?.strip_suffix(".log")?.parse().ok()
+}
+
+pub(crate) async fn analyze_log(args: &Args, client: &Client, log: &str, app_id: Option<u32>) -> Result<(), String> {
+    const LINES_COUNT: usize = 3;
+
+    // a bare app ID refers to the log Proton writes to the home directory
+    let path = match log.parse::<u32>() {
+        Ok(app_id) => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(format!("steam-{}.log", app_id)),
+        Err(_) => PathBuf::from(log),
+    };
+    let app_id = app_id.or(app_id_from_path(&path))
+        .ok_or("could not tell the app ID from the log file name, pass it with --app-id")?;
+    let bytes = fs::read(&path).map_err(|error| format!("could not read {}: {}", path.display(), error))?;
+    let text = String::from_utf8_lossy(&bytes);
+
+    let problems = find_problems(&text);
+    if problems.is_empty() {
+        println!("No known problems found in {}", path.display());
+        return Ok(());
+    }
+
+    let mut reports = fetch_reports(client, app_id, false).await.map(|reports| reports.reports).unwrap_or_default();
+    let device = args.device_filter();
+    reports.retain(|report| device.matches(&report.device));
+
+    for problem in problems {
+        print_heading(&problem.title);
+        for line in problem.lines.iter().take(LINES_COUNT) {
+            println!("  {}", line.truecolor(120, 120, 120));
+        }
+        if problem.lines.len() > LINES_COUNT {
+            println!("  {}", format!("… {} more lines", problem.lines.len() - LINES_COUNT).truecolor(120, 120, 120).italic());
+        }
+
+        let matches = matching_reports(&problem, &reports);
+        if matches.is_empty() {
+            println!("  {}", "No reports address this problem".truecolor(120, 120, 120).italic());
+            continue;
+        }
+        println!("  {}", format!("{} reports address this problem:", matches.len()).truecolor(200, 200, 200));
+        for candidate in matches.iter().take(args.reports as usize) {
+            let report = candidate.report;
+            let timestamp = chrono::DateTime::from_timestamp(report.timestamp as i64, 0).unwrap();
+            let verdict = if report.outcome().works() { "works".green() } else { "fails".red() };
+            println!("  {} {} {} {}",
+                "•".truecolor(120, 120, 120),
+                proton_name(&report.responses).bold(),
+                verdict,
+                format!("{} · {}", report.contributor.steam.nickname, HumanTime::from(timestamp)).truecolor(120, 120, 120)
+            );
+            if !candidate.customizations.is_empty() {
+                println!("    {} {}", "Used:".truecolor(200, 200, 200), candidate.customizations.join(", "));
+            }
+            if let Some(note) = &candidate.note {
+                println!("    {}", format!("\"{}\"", note).italic());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = include_str!("../tests/fixtures/steam-1091500.log");
+
+    #[test]
+    fn finds_missing_dlls() {
+        assert_eq!(missing_dll(r#"0024:err:module:import_dll Library MSVCP140.dll (which is needed by L"C:\\game.exe") not found"#), Some("msvcp140.dll".to_string()));
+        // the DLL is named, but the line only says importing failed
+        assert_eq!(missing_dll(r#"0024:err:module:loader_init Importing dlls for L"C:\\game.exe" failed, status c0000135"#), None);
+        assert_eq!(missing_dll("0024:fixme:module:import_dll Library MSVCP140.dll not found"), None);
+    }
+
+    #[test]
+    fn reads_wine_error_lines() {
+        assert_eq!(wine_error("0024:err:module:import_dll Library"), Some("module:import_dll Library"));
+        assert_eq!(wine_error("93.520:0020:0024:err:mfplat:resolver"), Some("mfplat:resolver"));
+        assert_eq!(wine_error("pressure-vessel-wrap[4711]: W: stderr is not a terminal"), None);
+        assert_eq!(wine_error("0024:fixme:vkd3d:deferred"), None);
+    }
+
+    #[test]
+    fn ignores_lines_that_only_look_like_errors() {
+        assert!(problem_kind("94.002:0020:0030:fixme:vkd3d:d3d12_command_queue_flush_deferred: deferred submission.").is_none());
+        assert!(problem_kind("pressure-vessel-wrap[4711]: W: stderr is not a terminal, output will be buffered").is_none());
+        assert!(problem_kind(r#"0034:trace:loaddll:build_module Loaded L"C:\\EasyAntiCheat\\EasyAntiCheat_x64.dll" at 00007FFA12340000: native"#).is_none());
+    }
+
+    #[test]
+    fn finds_problems_in_proton_log() {
+        let problems = find_problems(LOG);
+        let titles: Vec<&str> = problems.iter().map(|problem| problem.title.as_str()).collect();
+        assert_eq!(titles, [
+            "Missing DLL: msvcp140.dll",
+            "Missing DLL: vcruntime140.dll",
+            "VKD3D (DirectX 12) errors",
+            "Easy Anti-Cheat",
+            "Media Foundation (video playback)",
+        ]);
+        assert_eq!(problems[0].keywords, ["msvcp140.dll", "msvcp140", "vcrun"]);
+        assert_eq!(problems[2].lines.len(), 1);
+        assert_eq!(problems[4].lines.len(), 2);
+    }
+
+    #[test]
+    fn reads_app_id_from_log_name() {
+        assert_eq!(app_id_from_path(Path::new("/home/user/steam-1091500.log")), Some(1091500));
+        assert_eq!(app_id_from_path(Path::new("/home/user/proton.log")), None);
+    }
+}
@@ -3,90 +3,143 @@ mod post_body;
 mod args;
 mod utils;
 mod display_reports;
-
-use post_result::PostResult;
-use post_body::Body;
-use args::Args;
-use utils::{fetch_images, fetch_summaries, get_colored_steam_deck_status, get_colored_tier, is_query_id, print_image, Summary};
-use display_reports::{fetch_reports, Reports};
-
-use reqwest::ClientBuilder;
+mod hardware;
+mod stats;
+mod charts;
+mod launch_options;
+mod recommend;
+mod vdf;
+mod library;
+mod compat_tools;
+mod steam_config;
+mod tier;
+mod schema;
+mod compare;
+mod picker;
+mod tui;
+mod collections;
+mod log_analysis;
+mod protontricks;
+
+use args::{Args, Command, DeviceFilter};
+use utils::{build_client, fetch_images, fetch_summaries, format_summary_details, get_colored_tier, is_query_id, join_query, print_image, score_bar, search_hits, Summary};
+use display_reports::{fetch_all_reports, fetch_reports, Reports};
+use tier::{DeckStatus, Tier};
+
+use reqwest::Client;
 use clap::Parser;
 use colored::Colorize;
-use reqwest::header::HeaderMap;
 use futures::join;
 use image::DynamicImage;
 use crate::display_reports::display_reports;
+use crate::hardware::detect_specs;
+use crate::stats::{compute_stats, display_stats};
+use crate::charts::display_trend;
+use crate::launch_options::display_launch_options;
+use crate::protontricks::display_protontricks;
+use crate::schema::{display_schema_issues, display_skipped};
+use crate::recommend::recommend;
+use crate::library::display_library;
+use crate::compat_tools::display_tools;
+use crate::steam_config::apply_launch_options;
+use crate::collections::write_collections;
+use crate::log_analysis::analyze_log;
+use crate::compare::compare;
+use crate::picker::{is_interactive, pick_game};
+use crate::tui::run_tui;
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
-
-    let mut new_search = Vec::new();
-    for arg in &args.query {
-        if arg.contains(" ") {
-            let split = arg.split_whitespace();
-            new_search.extend(split.map(|s| s.to_string()));
-        } else {
-            new_search.push(arg.clone());
+    let client = build_client();
+
+    match &args.command {
+        Some(Command::Recommend { query }) => recommend(&args, &client, &join_query(query)).await,
+        Some(Command::Library) => display_library(&args, &client).await,
+        Some(Command::Tools { query }) => display_tools(&args, &client, &join_query(query)).await,
+        Some(Command::ApplyLaunchOptions { app_id, from_report, user, yes }) => {
+            apply_launch_options(&args, &client, *app_id, from_report, user.as_deref(), *yes).await.unwrap_or_else(|error| fail(&error))
+        }
+        Some(Command::Collections { user, dry_run }) => {
+            write_collections(&args, &client, user.as_deref(), *dry_run).await.unwrap_or_else(|error| fail(&error))
         }
+        Some(Command::Tui { query }) => run_tui(&args, &client, &join_query(query)).await,
+        Some(Command::Compare { games }) => compare(&args, &client, games).await,
+        Some(Command::AnalyzeLog { log, app_id }) => analyze_log(&args, &client, log, *app_id).await.unwrap_or_else(|error| fail(&error)),
+        None => search(&args, &client).await,
     }
-    let query = new_search.join(" ");
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{} {}", "error:".red().bold(), message);
+    std::process::exit(1);
+}
 
-    let should_display_reports = args.count == 1 || is_query_id(&query);
+async fn search(args: &Args, client: &Client) {
+    let query = join_query(&args.query);
 
-    let body = Body {
-        query: query.to_string(),
-        facet_filters: vec![vec!["appType:Game"]],
-        hits_per_page: args.count,
-        attributes_to_retrieve: vec!["name", "objectID", "oslist"],
-        page: 0
-    };
+    let mut should_display_reports = args.count == 1 || is_query_id(&query);
+    let on_steam_deck = args.on_steam_deck();
 
-    let client = ClientBuilder::new()
-        .default_headers(
-            HeaderMap::from_iter(
-                vec![
-                    ("x-algolia-api-key", "9ba0e69fb2974316cdaec8f5f257088f"),
-                    ("x-algolia-application-id", "94HE6YATEI"),
-                    ("Referer", "https://www.protondb.com")
-                ].into_iter().map(|(k, v)| (k.parse().unwrap(), v.parse().unwrap()))
-            )
-        ).build().unwrap();
-    let res = client
-        .post("https://94he6yatei-dsn.algolia.net/1/indexes/steamdb/query")
-        .body(serde_json::to_string(&body).unwrap())
-        .send()
-        .await.unwrap().json::<PostResult>().await.unwrap();
-
-    let steam_ids: Vec<String> = res.hits.iter().map(|game| game.object_id.clone()).collect();
+    let mut hits = search_hits(client, &query, args.count).await;
+    if is_query_id(&query) && hits.first().is_none_or(|hit| hit.object_id != query) {
+        println!("No game with app ID {} found", query);
+        return;
+    }
+    if hits.is_empty() {
+        println!("No games found");
+        return;
+    }
+
+    let mut picked_summaries = None;
+    if args.interactive && !should_display_reports && hits.len() > 1 && is_interactive() {
+        let steam_ids: Vec<String> = hits.iter().map(|game| game.object_id.clone()).collect();
+        let mut summaries = fetch_summaries(client, &steam_ids).await;
+        let Some(index) = pick_game(&hits, &summaries, args.prefer) else { return };
+        hits = vec![hits.swap_remove(index)];
+        picked_summaries = Some(vec![summaries.swap_remove(index)]);
+        should_display_reports = true;
+    }
+
+    let steam_ids: Vec<String> = hits.iter().map(|game| game.object_id.clone()).collect();
+    // the picker already fetched the summary of the game it picked
+    let summaries_future = async {
+        match picked_summaries {
+            Some(summaries) => summaries,
+            None => fetch_summaries(client, &steam_ids).await,
+        }
+    };
 
     let summaries: Vec<Option<Summary>>;
     let mut images: Vec<DynamicImage> = Vec::new();
-    let mut reports = Reports {
-        page: 0,
-        per_page: 0,
-        reports: Vec::new(),
-        total: 0
-    };
+    let mut reports: Option<Reports> = None;
 
     let steam_id: u32 = steam_ids.first().unwrap().parse().unwrap();
+    // statistics and trends over the newest page alone would only cover the last few weeks
+    let reports_future = async {
+        if args.stats || args.trend {
+            fetch_all_reports(client, steam_id, args.debug_schema).await
+        } else {
+            fetch_reports(client, steam_id, args.debug_schema).await
+        }
+    };
     if args.images {
         if should_display_reports {
-            (summaries, images, reports) = join!(fetch_summaries(&client, &steam_ids), fetch_images(&client, &steam_ids), fetch_reports(&client, steam_id));
+            (summaries, images, reports) = join!(summaries_future, fetch_images(client, &steam_ids), reports_future);
         } else {
-            (summaries, images) = join!(fetch_summaries(&client, &steam_ids), fetch_images(&client, &steam_ids));
+            (summaries, images) = join!(summaries_future, fetch_images(client, &steam_ids));
         }
     } else {
-        if args.count == 1 {
-            (summaries, reports) = join!(fetch_summaries(&client, &steam_ids), fetch_reports(&client, steam_id));
+        if should_display_reports {
+            (summaries, reports) = join!(summaries_future, reports_future);
         } else {
-            summaries = fetch_summaries(&client, &steam_ids).await;
+            summaries = summaries_future.await;
         }
     }
 
     const IMAGE_WIDTH: u32 = 14;
     const IMAGE_HEIGHT: u32 = 3;
+    const SCORE_BAR_WIDTH: usize = 20;
     let mut lines_printed;
     macro_rules! label {
         ($label:expr, $value:expr) => {
@@ -103,7 +156,7 @@ async fn main() {
         };
     }
 
-    for (index, game) in res.hits.into_iter().enumerate() {
+    for (index, game) in hits.into_iter().enumerate() {
         lines_printed = 0;
         if index != 0 { println!() }
 
@@ -111,16 +164,20 @@ async fn main() {
 
         label!(game.name.bold());
 
+        let steam_deck_status = DeckStatus::from_oslist(&game.oslist).map(|status| status.colored());
+        if on_steam_deck {
+            label!("Steam Deck", steam_deck_status.clone().map(|status| status.bold()).unwrap_or("Unknown".truecolor(120, 120, 120)));
+        }
+
         if let Some(summary) = &summaries[index] {
-            label!("Rating", get_colored_tier(&summary.tier, &game.oslist));
+            label!("Rating", format!("{} {}", get_colored_tier(&summary.tier, &game.oslist, args.prefer), format_summary_details(summary)));
+            label!("Score", score_bar(summary.score, SCORE_BAR_WIDTH));
 
-            let steam_deck_status = game.oslist.iter().find(|os| os.starts_with("Steam Deck"));
-            if let Some(status) = steam_deck_status {
-                let status = get_colored_steam_deck_status(status);
+            if let Some(status) = steam_deck_status.filter(|_| !on_steam_deck) {
                 label!("Steam Deck", status);
             }
         } else {
-            label!("Rating", get_colored_tier(&"pending".to_string(), &game.oslist));
+            label!("Rating", get_colored_tier(&Tier::Pending, &game.oslist, args.prefer));
         }
 
         if args.images && IMAGE_HEIGHT > lines_printed {
@@ -129,7 +186,45 @@ async fn main() {
     }
 
     if should_display_reports {
+        let Some(mut reports) = reports else {
+            println!();
+            println!("{}", "No reports could be loaded for this game".truecolor(120, 120, 120).italic());
+            return;
+        };
         let termsize::Size { rows: _, cols: width } = termsize::get().unwrap();
-        display_reports(reports, &args, &client, width).await;
+        let local_specs = args.similar.then(|| detect_specs(&args.sysroot));
+        if let Some(specs) = &local_specs {
+            println!();
+            println!("{} {} · {} · {}", "Your system:".truecolor(200, 200, 200), specs.gpu, specs.gpu_driver, specs.os);
+        }
+
+        let device = args.device_filter();
+        let total = reports.reports.len();
+        reports.reports.retain(|report| device.matches(&report.device));
+        if device != DeviceFilter::All {
+            println!();
+            println!("{}", format!("Showing {} reports ({} of {})", device, reports.reports.len(), total).truecolor(120, 120, 120).italic());
+        }
+
+        display_skipped(&reports);
+
+        if args.debug_schema {
+            display_schema_issues(&reports, width);
+        }
+        if args.stats {
+            display_stats(&compute_stats(&reports.reports), width);
+        }
+        if args.trend {
+            display_trend(&reports.reports, width);
+        }
+        if args.launch_options {
+            display_launch_options(&reports.reports, width);
+        }
+        if args.protontricks {
+            display_protontricks(&reports.reports, steam_id, width);
+        }
+        if !args.stats && !args.trend && !args.launch_options && !args.protontricks && !args.debug_schema {
+            display_reports(reports, args, client, width, local_specs.as_ref()).await;
+        }
     }
 }
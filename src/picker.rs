@@ -0,0 +1,100 @@
+use std::io::{stdout, IsTerminal, Write};
+use colored::Colorize;
+use crossterm::{cursor, execute, queue, terminal};
+use crossterm::event::{read, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crate::args::Prefer;
+use crate::post_result::Game;
+use crate::tier::Tier;
+use crate::utils::{get_colored_tier, Summary};
+
+pub(crate) fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && stdout().is_terminal()
+}
+
+// every character of the filter has to appear in the name, in order
+pub(crate) fn fuzzy_matches(name: &str, filter: &str) -> bool {
+    let mut name = name.chars().flat_map(char::to_lowercase);
+    filter.chars().flat_map(char::to_lowercase).all(|c| name.any(|n| n == c))
+}
+
+fn render(games: &[Game], summaries: &[Option<Summary>], prefer: Prefer, filter: &str, visible: &[usize], selected: usize) -> Vec<String> {
+    let mut lines = vec![format!("{} {}", "Pick a game:".truecolor(200, 200, 200), filter)];
+    for (position, &index) in visible.iter().enumerate() {
+        let tier = summaries[index].as_ref().map(|summary| summary.tier.clone()).unwrap_or(Tier::Pending);
+        let badge = get_colored_tier(&tier, &games[index].oslist, prefer);
+        let name = if position == selected { format!("› {}", games[index].name).bold() } else { format!("  {}", games[index].name).normal() };
+        lines.push(format!("{} {}", name, badge));
+    }
+    if visible.is_empty() {
+        lines.push("  No matching games".truecolor(120, 120, 120).italic().to_string());
+    }
+    lines.push("↑/↓ to move, type to filter, enter to pick, esc to cancel".truecolor(120, 120, 120).to_string());
+    lines
+}
+
+// leaves raw mode however the picker is left, including on panics
+struct RawModeGuard;
+
+impl RawModeGuard {
+    fn enter() -> Option<RawModeGuard> {
+        terminal::enable_raw_mode().ok()?;
+        Some(RawModeGuard)
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// lets the user pick one of the search hits, returns its index or None when cancelled
+pub(crate) fn pick_game(games: &[Game], summaries: &[Option<Summary>], prefer: Prefer) -> Option<usize> {
+    let mut stdout = stdout();
+    let mut filter = String::new();
+    let mut selected = 0;
+    let mut lines_drawn = 0;
+
+    let guard = RawModeGuard::enter()?;
+    let picked = loop {
+        let visible: Vec<usize> = (0..games.len()).filter(|&index| fuzzy_matches(&games[index].name, &filter)).collect();
+        selected = selected.min(visible.len().saturating_sub(1));
+
+        // redraw in place over the previous frame
+        if lines_drawn > 0 {
+            queue!(stdout, cursor::MoveToPreviousLine(lines_drawn)).unwrap();
+        }
+        queue!(stdout, terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+        let lines = render(games, summaries, prefer, &filter, &visible, selected);
+        for line in &lines {
+            // raw mode doesn't move back to the start of the line on \n
+            write!(stdout, "{}\r\n", line).unwrap();
+        }
+        lines_drawn = lines.len() as u16;
+        stdout.flush().unwrap();
+
+        let event = match read() {
+            Ok(event) => event,
+            // the terminal went away, there is nobody left to pick
+            Err(_) => break None,
+        };
+        let Event::Key(KeyEvent { code, modifiers, kind: KeyEventKind::Press, .. }) = event else { continue };
+        match code {
+            KeyCode::Enter if !visible.is_empty() => break Some(visible[selected]),
+            KeyCode::Esc => break None,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break None,
+            KeyCode::Up => selected = selected.saturating_sub(1),
+            KeyCode::Down => selected += 1,
+            KeyCode::Backspace => { filter.pop(); }
+            KeyCode::Char(c) => {
+                filter.push(c);
+                selected = 0;
+            }
+            _ => {}
+        }
+    };
+    drop(guard);
+
+    execute!(stdout, cursor::MoveToPreviousLine(lines_drawn), terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+    picked
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use colored::Colorize;
+use crate::display_reports::Report;
+use crate::stats::{percentage, print_heading};
+
+// winetricks verbs that are commonly named without a version suffix
+const VERBS: [&str; 24] = [
+    "d3dx9", "d3dx10", "xact", "xact_x64", "xinput", "faudio", "quartz", "lavfilters", "physx", "corefonts",
+    "allfonts", "directplay", "directmusic", "dsound", "dinput8", "gdiplus", "vb6run", "openal", "devenum",
+    "amstream", "cnc_ddraw", "win7", "win10", "winxp",
+];
+
+// verbs that are a fixed prefix followed by a version number (vcrun2019, d3dcompiler_47, dotnet48, ...)
+const VERSIONED_VERBS: [&str; 10] = [
+    "vcrun", "dotnetdesktop", "dotnet", "d3dcompiler_", "d3dx9_", "d3dx10_", "d3dx11_", "msxml", "wmp", "mfc",
+];
+
+pub(crate) struct VerbUsage {
+    pub verb: String,
+    pub count: usize,
+    pub works: usize,
+}
+
+fn is_verb(word: &str) -> bool {
+    VERBS.contains(&word) || VERSIONED_VERBS.iter().any(|prefix| {
+        word.strip_prefix(prefix).is_some_and(|version| !version.is_empty() && version.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+pub(crate) fn extract_verbs(text: &str) -> Vec<String> {
+    let mut verbs: Vec<String> = text
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .map(|word| word.to_lowercase())
+        .filter(|word| is_verb(word))
+        .collect();
+    verbs.sort();
+    verbs.dedup();
+    verbs
+}
+
+pub(crate) fn verb_usage(reports: &[Report]) -> Vec<VerbUsage> {
+    let mut usage: HashMap<String, (usize, usize)> = HashMap::new();
+    for report in reports {
+        let notes = [&report.responses.notes.tinker_override, &report.responses.concluding_notes];
+        let text: Vec<&str> = notes.into_iter().filter_map(|note| note.as_deref()).collect();
+        let works = report.outcome().works();
+
+        // count each verb once per report
+        for verb in extract_verbs(&text.join("\n")) {
+            let entry = usage.entry(verb).or_insert((0, 0));
+            entry.0 += 1;
+            if works { entry.1 += 1 }
+        }
+    }
+
+    let mut usage: Vec<_> = usage.into_iter()
+        .map(|(verb, (count, works))| VerbUsage { verb, count, works })
+        .collect();
+    usage.sort_by(|a, b| b.count.cmp(&a.count).then(b.works.cmp(&a.works)).then(a.verb.cmp(&b.verb)));
+    usage
+}
+
+pub(crate) fn display_protontricks(reports: &[Report], app_id: u32, terminal_width: u16) {
+    const TOP_COUNT: usize = 10;
+
+    println!("{}", "—".repeat(terminal_width as usize));
+
+    let customized = reports.iter()
+        .filter_map(|report| report.responses.customizations_used.as_ref())
+        .filter(|customizations| customizations.winetricks.unwrap_or(false) || customizations.protontricks.unwrap_or(false))
+        .count();
+    println!("{} {} of {} reports used winetricks or protontricks",
+        "Protontricks:".truecolor(200, 200, 200),
+        customized,
+        reports.len()
+    );
+
+    let usage = verb_usage(reports);
+    if usage.is_empty() {
+        println!("{}", "No verbs mentioned in report notes".truecolor(120, 120, 120).italic());
+        return;
+    }
+
+    print_heading("Most Common Verbs");
+    let width = usage.iter().take(TOP_COUNT).map(|usage| usage.verb.len()).max().unwrap_or(0);
+    for usage in usage.iter().take(TOP_COUNT) {
+        let works = format!("{} works ({}%)", usage.works, percentage(usage.works, usage.count));
+        println!("  {:<width$}  {:>3} {}  {}",
+            format!("protontricks {} {}", app_id, usage.verb),
+            usage.count,
+            if usage.count == 1 { "report " } else { "reports" },
+            if usage.works * 2 >= usage.count { works.green() } else { works.yellow() },
+            width = width + format!("protontricks {} ", app_id).len()
+        );
+    }
+
+    // verbs mentioned by more than one working report, in one command
+    let verbs: Vec<&str> = usage.iter()
+        .filter(|usage| usage.works > 1)
+        .take(TOP_COUNT)
+        .map(|usage| usage.verb.as_str())
+        .collect();
+    if verbs.len() > 1 {
+        print_heading("All Together");
+        println!("  protontricks {} {}", app_id, verbs.join(" "));
+    }
+}
@@ -0,0 +1,179 @@
+use colored::Colorize;
+use futures::join;
+use reqwest::Client;
+use crate::args::{Args, DeviceFilter};
+use crate::compat_tools::{find_installed, installed_tools, InstalledTool};
+use crate::display_reports::{fetch_reports, Report, Responses};
+use crate::hardware::{detect_specs, matching_specs, SIMILAR_SYSTEM_THRESHOLD};
+use crate::launch_options::{launch_option_usage, TokenKind};
+use crate::stats::percentage;
+use crate::library::steam_roots;
+use crate::utils::{fetch_summaries, format_summary_details, get_colored_tier, resolve_game};
+
+pub(crate) struct ProtonChoice {
+    pub name: String,
+    pub reports: usize,
+    pub works: usize,
+    pub launch_options: Vec<String>,
+}
+
+impl ProtonChoice {
+    // smoothed success rate so that a single working report doesn't outrank a well tested version
+    pub fn score(&self) -> f64 {
+        (self.works as f64 + 1.0) / (self.reports as f64 + 2.0)
+    }
+}
+
+pub(crate) fn proton_name(responses: &Responses) -> String {
+    match responses.variant.as_deref() {
+        Some("experimental") => "Proton Experimental".to_string(),
+        Some("ge") => responses.custom_proton_version.clone().unwrap_or("GE-Proton".to_string()),
+        Some("notListed") => responses.custom_proton_version.clone().unwrap_or("Custom Proton".to_string()),
+        _ => format!("Proton {}", responses.proton_version),
+    }
+}
+
+// point releases like 8.0-4 and 8.0-5 count as the same version when recommending one
+pub(crate) fn proton_release(responses: &Responses) -> String {
+    let name = proton_name(responses);
+    match name.strip_prefix("Proton ") {
+        Some(version) if version.starts_with(|c: char| c.is_ascii_digit()) => {
+            format!("Proton {}", version.split('-').next().unwrap_or(version))
+        }
+        _ => name,
+    }
+}
+
+pub(crate) fn recommend_proton(reports: &[Report]) -> Vec<ProtonChoice> {
+    const LAUNCH_OPTIONS_COUNT: usize = 3;
+
+    let mut names: Vec<String> = reports.iter().map(|report| proton_release(&report.responses)).collect();
+    names.sort();
+    names.dedup();
+
+    let mut choices: Vec<ProtonChoice> = names.into_iter()
+        .map(|name| {
+            let reports: Vec<Report> = reports.iter()
+                .filter(|report| proton_release(&report.responses) == name)
+                .cloned()
+                .collect();
+            let working: Vec<Report> = reports.iter()
+                .filter(|report| report.outcome().works())
+                .cloned()
+                .collect();
+            let launch_options = launch_option_usage(&working).into_iter()
+                .filter(|usage| usage.kind != TokenKind::Command)
+                .take(LAUNCH_OPTIONS_COUNT)
+                .map(|usage| usage.token)
+                .collect();
+            ProtonChoice { name, reports: reports.len(), works: working.len(), launch_options }
+        })
+        .collect();
+
+    choices.sort_by(|a, b| b.score().total_cmp(&a.score()).then(b.reports.cmp(&a.reports)));
+    choices
+}
+
+fn print_choice(choice: &ProtonChoice, highlight: bool, installed: Option<&InstalledTool>) {
+    let name = if highlight { choice.name.bold().green() } else { choice.name.normal() };
+    println!("  {} {} {}",
+        name,
+        format!("{}/{} reports work ({}%)", choice.works, choice.reports, percentage(choice.works, choice.reports)).truecolor(120, 120, 120),
+        installed.map(|tool| format!("[installed: {}]", tool.display_name).cyan()).unwrap_or_default()
+    );
+    if !choice.launch_options.is_empty() {
+        println!("    {} {}", "Launch options:".truecolor(200, 200, 200), choice.launch_options.join(" "));
+    }
+}
+
+pub(crate) async fn recommend(args: &Args, client: &Client, query: &str) {
+    const ALTERNATIVES_COUNT: usize = 4;
+
+    let Some(game) = resolve_game(client, query).await else {
+        println!("No games found");
+        return;
+    };
+    println!("{}", game.name.bold());
+
+    let (summaries, reports) = join!(
+        fetch_summaries(client, std::slice::from_ref(&game.object_id)),
+        fetch_reports(client, game.object_id.parse().unwrap(), false)
+    );
+    if let Some(summary) = summaries.into_iter().next().flatten() {
+        println!("{} {} {}", "Rating:".truecolor(200, 200, 200), get_colored_tier(&summary.tier, &game.oslist, args.prefer), format_summary_details(&summary));
+    }
+    // games without reports end up with no choices below
+    let mut reports = reports.map(|reports| reports.reports).unwrap_or_default();
+
+    let device = args.device_filter();
+    reports.retain(|report| device.matches(&report.device));
+    if device != DeviceFilter::All {
+        println!("{}", format!("Based on {} reports", device).truecolor(120, 120, 120).italic());
+    }
+
+    if args.similar {
+        let local_specs = detect_specs(&args.sysroot);
+        let similar: Vec<Report> = reports.iter()
+            .filter(|report| matching_specs(&local_specs, &report.device.inferred.steam).len() >= SIMILAR_SYSTEM_THRESHOLD)
+            .cloned()
+            .collect();
+        if similar.is_empty() {
+            println!("{}", "No reports from similar systems, using all reports".truecolor(120, 120, 120).italic());
+        } else {
+            println!("{}", format!("Based on {} reports from systems similar to yours", similar.len()).truecolor(120, 120, 120).italic());
+            reports = similar;
+        }
+    }
+
+    let choices = recommend_proton(&reports);
+    let Some(best) = choices.first() else {
+        println!("No reports to base a recommendation on");
+        return;
+    };
+
+    let tools = installed_tools(&steam_roots(args), &args.sysroot);
+
+    println!();
+    println!("{}", "Recommended:".truecolor(200, 200, 200));
+    let installed = find_installed(&tools, &best.name);
+    print_choice(best, true, installed);
+    // the choice line already says when it is installed
+    if installed.is_none() && !tools.is_empty() {
+        println!("  {}", format!("{} is not installed", best.name).yellow());
+    }
+
+    if choices.len() > 1 {
+        println!();
+        println!("{}", "Alternatives:".truecolor(200, 200, 200));
+        for choice in choices.iter().skip(1).take(ALTERNATIVES_COUNT) {
+            print_choice(choice, false, find_installed(&tools, &choice.name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_reports::parse_reports;
+
+    #[test]
+    fn groups_point_releases() {
+        let reports = parse_reports(include_str!("../tests/fixtures/reports.json"), false).unwrap().reports;
+        assert_eq!(proton_name(&reports[0].responses), "Proton 8.0-4");
+        assert_eq!(proton_release(&reports[0].responses), "Proton 8.0");
+
+        let mut names: Vec<(String, usize)> = recommend_proton(&reports).into_iter().map(|choice| (choice.name, choice.reports)).collect();
+        names.sort();
+        assert_eq!(names, [("Proton 8.0".to_string(), 6), ("Proton 9.0".to_string(), 6)]);
+    }
+
+    #[test]
+    fn keeps_custom_names() {
+        let mut responses = parse_reports(include_str!("../tests/fixtures/reports.json"), false).unwrap().reports.remove(0).responses;
+        responses.variant = Some("ge".to_string());
+        responses.custom_proton_version = Some("GE-Proton9-4".to_string());
+        assert_eq!(proton_release(&responses), "GE-Proton9-4");
+        responses.variant = Some("experimental".to_string());
+        assert_eq!(proton_release(&responses), "Proton Experimental");
+    }
+}
@@ -0,0 +1,81 @@
+use colored::Colorize;
+use serde_json::Value;
+use crate::display_reports::{Answer, Reports};
+use crate::stats::{count_sorted, print_heading};
+
+// decoded by a custom function into a bool, so the raw value never matches the re-encoded one
+const CONVERTED_FIELDS: [&str; 1] = ["responses.followUp.inputFaults.other"];
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) }
+}
+
+// compares a report as sent with the same report decoded and encoded again: anything lost on the way is unknown
+pub(crate) fn schema_issues(raw: &Value, decoded: &Value, path: &str, issues: &mut Vec<String>) {
+    match (raw, decoded) {
+        (Value::Object(raw), Value::Object(decoded)) => {
+            for (key, raw_value) in raw {
+                let path = join_path(path, key);
+                match decoded.get(key) {
+                    Some(decoded_value) => schema_issues(raw_value, decoded_value, &path, issues),
+                    None => issues.push(format!("unknown field {}", path)),
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(decoded)) => {
+            for (raw_value, decoded_value) in raw.iter().zip(decoded) {
+                schema_issues(raw_value, decoded_value, path, issues);
+            }
+        }
+        _ if raw == decoded || CONVERTED_FIELDS.contains(&path) => {}
+        _ => issues.push(format!("unrecognized value {} = {}", path, raw)),
+    }
+
+    if path == "responses" {
+        if let Value::Object(raw) = raw {
+            unexpected_answers(raw, issues);
+        }
+    }
+}
+
+// answers are decoded as Answer::Other when they aren't "yes" or "no", which survives re-encoding unchanged
+fn unexpected_answers(responses: &serde_json::Map<String, Value>, issues: &mut Vec<String>) {
+    const ANSWER_FIELDS: [&str; 18] = [
+        "installs", "opens", "verdict", "verdictOob", "startsPlay", "triedOob", "tinkerOverride",
+        "audioFaults", "graphicalFaults", "inputFaults", "performanceFaults", "saveGameFaults",
+        "significantBugs", "stabilityFaults", "windowingFaults", "batteryPerformance",
+        "didChangeControlLayout", "readability",
+    ];
+    for field in ANSWER_FIELDS {
+        let Some(Value::String(answer)) = responses.get(field) else { continue };
+        if let Answer::Other(answer) = Answer::from(answer.clone()) {
+            issues.push(format!("unrecognized answer responses.{} = \"{}\"", field, answer));
+        }
+    }
+}
+
+pub(crate) fn display_skipped(reports: &Reports) {
+    if reports.skipped > 0 {
+        println!("{}", format!("{} reports could not be read and were skipped (see --debug-schema)", reports.skipped).yellow().italic());
+    }
+}
+
+pub(crate) fn display_schema_issues(reports: &Reports, terminal_width: u16) {
+    println!("{}", "—".repeat(terminal_width as usize));
+    println!("{} {} of {} reports decoded, {} skipped",
+        "Schema:".truecolor(200, 200, 200),
+        reports.decoded,
+        reports.decoded + reports.skipped,
+        reports.skipped
+    );
+    if reports.schema_issues.is_empty() {
+        println!("{}", "No unknown fields or values".truecolor(120, 120, 120).italic());
+        return;
+    }
+
+    print_heading("Unknown Fields and Values");
+    for (issue, count) in count_sorted(reports.schema_issues.iter()) {
+        println!("  {:>4}×  {}", count, issue);
+    }
+}
+
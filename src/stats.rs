@@ -0,0 +1,189 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use colored::{Color, Colorize};
+use crate::display_reports::{Answer, Outcome, Report};
+use crate::hardware::gpu_vendor;
+
+pub(crate) struct FaultStats {
+    pub category: &'static str,
+    pub count: usize,
+    pub details: Vec<(String, usize)>,
+}
+
+pub(crate) struct Stats {
+    pub total: usize,
+    pub recommended: usize,
+    pub not_recommended: usize,
+    pub borked: usize,
+    pub out_of_the_box: usize,
+    pub tinker: usize,
+    pub faults: Vec<FaultStats>,
+    pub proton_versions: Vec<(String, usize)>,
+    pub gpu_vendors: Vec<(&'static str, usize)>,
+    pub reports_per_month: Vec<(String, usize)>,
+}
+
+pub(crate) fn count_sorted<K: Hash + Eq + Ord>(items: impl IntoIterator<Item = K>) -> Vec<(K, usize)> {
+    let mut counts = HashMap::new();
+    for item in items {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|(a_key, a_count), (b_key, b_count)| b_count.cmp(a_count).then(a_key.cmp(b_key)));
+    counts
+}
+
+pub(crate) fn report_month(report: &Report) -> String {
+    chrono::DateTime::from_timestamp(report.timestamp as i64, 0).unwrap().format("%Y-%m").to_string()
+}
+
+fn fault_stats(reports: &[Report]) -> Vec<FaultStats> {
+    let mut faults = Vec::new();
+
+    macro_rules! count_faults {
+        ($faults:ident, $category:expr) => {
+            let affected: Vec<_> = reports.iter()
+                .filter(|report| report.responses.$faults.as_ref().is_some_and(Answer::is_yes))
+                .collect();
+            let details = count_sorted(affected.iter()
+                .filter_map(|report| report.responses.follow_up.as_ref()?.$faults.as_ref())
+                .flat_map(|faults| faults.keys()));
+            faults.push(FaultStats { category: $category, count: affected.len(), details });
+        };
+    }
+
+    count_faults!(audio_faults, "Audio");
+    count_faults!(graphical_faults, "Graphics");
+    count_faults!(windowing_faults, "Windowing");
+    count_faults!(input_faults, "Input");
+    count_faults!(save_game_faults, "Save Game");
+    count_faults!(performance_faults, "Performance");
+    count_faults!(stability_faults, "Stability");
+
+    let significant_bugs = reports.iter()
+        .filter(|report| report.responses.significant_bugs.as_ref().is_some_and(Answer::is_yes))
+        .count();
+    faults.push(FaultStats { category: "Significant Bugs", count: significant_bugs, details: Vec::new() });
+
+    faults.retain(|fault| fault.count > 0);
+    faults.sort_by_key(|fault| std::cmp::Reverse(fault.count));
+    faults
+}
+
+pub(crate) fn compute_stats(reports: &[Report]) -> Stats {
+    let outcomes: Vec<Outcome> = reports.iter().map(|report| report.outcome()).collect();
+    let count = |outcome: Outcome| outcomes.iter().filter(|other| **other == outcome).count();
+
+    let mut reports_per_month = BTreeMap::new();
+    for report in reports {
+        *reports_per_month.entry(report_month(report)).or_insert(0) += 1;
+    }
+
+    Stats {
+        total: reports.len(),
+        recommended: outcomes.iter().filter(|outcome| outcome.works()).count(),
+        not_recommended: count(Outcome::NotRecommended),
+        borked: count(Outcome::Borked),
+        out_of_the_box: count(Outcome::WorksOutOfTheBox),
+        tinker: count(Outcome::WorksWithTinkering),
+        faults: fault_stats(reports),
+        proton_versions: count_sorted(reports.iter().map(|report| report.responses.proton_version.clone())),
+        gpu_vendors: count_sorted(reports.iter().map(|report| gpu_vendor(&report.device.inferred.steam.gpu))),
+        reports_per_month: reports_per_month.into_iter().collect(),
+    }
+}
+
+pub(crate) fn percentage(count: usize, total: usize) -> usize {
+    if total == 0 { 0 } else { (count as f64 / total as f64 * 100.0).round() as usize }
+}
+
+const LABEL_WIDTH: usize = 18;
+
+fn print_bar(label: &str, count: usize, total: usize, max: usize, width: u16, color: Color) {
+    // leave room for the label and the "count (percentage)" suffix
+    let bar_width = (width as usize).saturating_sub(LABEL_WIDTH + 16).max(10);
+    let length = (count * bar_width).checked_div(max).unwrap_or(0);
+    println!("  {:<LABEL_WIDTH$} {} {} {}",
+        label,
+        "█".repeat(length.max(if count > 0 { 1 } else { 0 })).color(color),
+        count,
+        format!("({}%)", percentage(count, total)).truecolor(120, 120, 120)
+    );
+}
+
+pub(crate) fn print_heading(heading: &str) {
+    println!();
+    println!("{}", heading.bold());
+}
+
+pub(crate) fn display_stats(stats: &Stats, terminal_width: u16) {
+    const TOP_COUNT: usize = 5;
+
+    println!("{}", "—".repeat(terminal_width as usize));
+    println!("{} {}", "Reports:".truecolor(200, 200, 200), stats.total);
+
+    print_heading("Verdicts");
+    let max = stats.recommended.max(stats.not_recommended).max(stats.borked);
+    print_bar("Recommended", stats.recommended, stats.total, max, terminal_width, Color::Green);
+    print_bar("Not Recommended", stats.not_recommended, stats.total, max, terminal_width, Color::Yellow);
+    print_bar("Borked", stats.borked, stats.total, max, terminal_width, Color::Red);
+
+    print_heading("Out of the Box vs Tinkering");
+    let playable = stats.out_of_the_box + stats.tinker;
+    let max = stats.out_of_the_box.max(stats.tinker);
+    print_bar("Out of the Box", stats.out_of_the_box, playable, max, terminal_width, Color::Green);
+    print_bar("Tinkering", stats.tinker, playable, max, terminal_width, Color::Yellow);
+
+    if !stats.faults.is_empty() {
+        print_heading("Faults");
+        for fault in &stats.faults {
+            let details = fault.details.iter()
+                .map(|(key, count)| format!("{} {}", key, count))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("  {:<LABEL_WIDTH$} {} {} {}",
+                fault.category,
+                fault.count,
+                format!("({}%)", percentage(fault.count, stats.total)).truecolor(120, 120, 120),
+                details.truecolor(200, 200, 200)
+            );
+        }
+    }
+
+    print_heading("Top Proton Versions");
+    let max = stats.proton_versions.first().map_or(0, |(_, count)| *count);
+    for (version, count) in stats.proton_versions.iter().take(TOP_COUNT) {
+        print_bar(version, *count, stats.total, max, terminal_width, Color::Cyan);
+    }
+
+    print_heading("GPU Vendors");
+    let max = stats.gpu_vendors.first().map_or(0, |(_, count)| *count);
+    for (vendor, count) in &stats.gpu_vendors {
+        print_bar(vendor, *count, stats.total, max, terminal_width, Color::Magenta);
+    }
+
+    print_heading("Reports per Month");
+    let max = stats.reports_per_month.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    for (month, count) in &stats.reports_per_month {
+        print_bar(month, *count, stats.total, max, terminal_width, Color::Blue);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_reports::parse_reports;
+
+    #[test]
+    fn counts_outcomes_like_every_other_view() {
+        let reports = parse_reports(include_str!("../tests/fixtures/reports.json"), false).unwrap().reports;
+        let stats = compute_stats(&reports);
+        assert_eq!(stats.total, 12);
+        assert_eq!((stats.recommended, stats.not_recommended, stats.borked), (10, 1, 1));
+        assert_eq!((stats.out_of_the_box, stats.tinker), (7, 3));
+
+        let out_of_the_box = reports.iter().filter(|report| report.outcome() == Outcome::WorksOutOfTheBox).count();
+        assert_eq!(stats.out_of_the_box, out_of_the_box);
+        assert_eq!(stats.gpu_vendors[0], ("AMD", 6));
+    }
+}
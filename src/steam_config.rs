@@ -0,0 +1,266 @@
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use colored::Colorize;
+use reqwest::Client;
+use crate::args::Args;
+use crate::display_reports::{fetch_counts, fetch_reports_page, is_last_page, Report};
+use crate::library::steam_roots;
+use crate::vdf;
+
+pub(crate) struct LaunchOptionsChange {
+    pub path: PathBuf,
+    pub old: Option<String>,
+    pub new: String,
+    pub config: vdf::Value,
+}
+
+// Steam keeps its config in memory and overwrites the files on exit, so they must not be edited while it runs
+pub(crate) fn is_steam_running(sysroot: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(sysroot.join("proc")) else { return false };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().chars().all(|c| c.is_ascii_digit()))
+        .any(|entry| fs::read_to_string(entry.path().join("comm")).is_ok_and(|comm| comm.trim() == "steam"))
+}
+
+// userdata/<user id>/config for every Steam account that has logged in on this machine
+pub(crate) fn user_config_dirs(steam_roots: &[PathBuf], user: Option<&str>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for steam_root in steam_roots {
+        let Ok(entries) = fs::read_dir(steam_root.join("userdata")) else { continue };
+        let mut users: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| user.is_none_or(|user| entry.file_name().to_string_lossy() == user))
+            .map(|entry| entry.path().join("config"))
+            .filter(|dir| dir.is_dir())
+            .collect();
+        users.sort();
+        dirs.extend(users);
+    }
+    dirs
+}
+
+pub(crate) fn plan_launch_options(path: &Path, app_id: u32, launch_options: &str) -> Result<LaunchOptionsChange, String> {
+    let text = fs::read_to_string(path).map_err(|error| format!("could not read {}: {}", path.display(), error))?;
+    let mut config = vdf::parse(&text).map_err(|error| format!("could not parse {}: {}", path.display(), error))?;
+
+    let app_id = app_id.to_string();
+    let key_path = ["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", app_id.as_str(), "LaunchOptions"];
+    let old = config.get_path(&key_path).and_then(|value| value.as_str()).map(|value| value.to_string());
+    config.set_path(&key_path, vdf::Value::String(launch_options.to_string()));
+
+    Ok(LaunchOptionsChange { path: path.to_path_buf(), old, new: launch_options.to_string(), config })
+}
+
+// copies the file to <file>.<timestamp>.bak, numbering backups made within the same second instead of overwriting them
+fn create_backup(path: &Path) -> io::Result<PathBuf> {
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut number = 0;
+    loop {
+        let backup = match number {
+            0 => PathBuf::from(format!("{}.{}.bak", path.display(), timestamp)),
+            _ => PathBuf::from(format!("{}.{}-{}.bak", path.display(), timestamp, number)),
+        };
+        match OpenOptions::new().write(true).create_new(true).open(&backup) {
+            Ok(mut file) => {
+                io::copy(&mut File::open(path)?, &mut file)?;
+                return Ok(backup);
+            }
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => number += 1,
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+// writes to a temporary file next to the original and renames it over, so an interrupted write never leaves half a file
+pub(crate) fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temporary = PathBuf::from(format!("{}.tmp", path.display()));
+    fs::write(&temporary, contents)
+        .and_then(|_| fs::rename(&temporary, path))
+        .inspect_err(|_| { let _ = fs::remove_file(&temporary); })
+}
+
+// keeps a copy of the original file next to it before replacing it
+pub(crate) fn write_with_backup(path: &Path, contents: &[u8]) -> io::Result<PathBuf> {
+    let backup = create_backup(path)?;
+    write_atomically(path, contents)?;
+    Ok(backup)
+}
+
+pub(crate) fn print_diff(path: &Path, old: &[String], new: &[String]) {
+    println!("{}", path.display().to_string().bold());
+    for line in old {
+        println!("{}", format!("- {}", line).red());
+    }
+    for line in new {
+        println!("{}", format!("+ {}", line).green());
+    }
+}
+
+// plans the change for every user before anything is written, so one unreadable config doesn't leave the others half done
+pub(crate) fn plan_all_launch_options(config_dirs: &[PathBuf], app_id: u32, launch_options: &str) -> Result<Vec<LaunchOptionsChange>, String> {
+    config_dirs.iter()
+        .map(|config_dir| plan_launch_options(&config_dir.join("localconfig.vdf"), app_id, launch_options))
+        .collect()
+}
+
+// reports are paged, so older ones are only found by going through the following pages
+async fn find_report(client: &Client, app_id: u32, report_id: &str) -> Result<Report, String> {
+    let counts = fetch_counts(client).await;
+    let mut loaded = 0;
+    for page in 1.. {
+        let reports = match fetch_reports_page(client, app_id, &counts, page).await {
+            Some(reports) => reports,
+            None if page == 1 => return Err(format!("no reports could be loaded for app {}", app_id)),
+            None => break,
+        };
+        loaded += reports.reports.len();
+        let last = is_last_page(&reports, loaded);
+        if let Some(report) = reports.reports.into_iter().find(|report| report.id == report_id) {
+            return Ok(report);
+        }
+        if last { break }
+    }
+    Err(format!("report {} not found for app {}", report_id, app_id))
+}
+
+// without `yes` this only shows what would change
+pub(crate) async fn apply_launch_options(args: &Args, client: &Client, app_id: u32, report_id: &str, user: Option<&str>, yes: bool) -> Result<(), String> {
+    let report = find_report(client, app_id, report_id).await?;
+    let launch_options = report.responses.launch_options.as_ref()
+        .ok_or(format!("report {} doesn't use any launch options", report_id))?;
+
+    let config_dirs = user_config_dirs(&steam_roots(args), user);
+    if config_dirs.is_empty() {
+        return Err("no Steam user config found (use --steam-dir and --user to point to one)".to_string());
+    }
+    if yes && is_steam_running(&args.sysroot) {
+        return Err("Steam is running, close it first so it doesn't overwrite the change".to_string());
+    }
+
+    let changes: Vec<LaunchOptionsChange> = plan_all_launch_options(&config_dirs, app_id, launch_options)?
+        .into_iter()
+        .filter(|change| {
+            let unchanged = change.old.as_deref() == Some(change.new.as_str());
+            if unchanged {
+                println!("{} {}", change.path.display().to_string().bold(), "already uses these launch options".truecolor(120, 120, 120));
+            }
+            !unchanged
+        })
+        .collect();
+
+    let line = |value: &str| format!("\"LaunchOptions\"\t\t\"{}\"", value);
+    for change in &changes {
+        print_diff(&change.path, &change.old.iter().map(|old| line(old)).collect::<Vec<_>>(), &[line(&change.new)]);
+    }
+    if changes.is_empty() {
+        return Ok(());
+    }
+    // the launch options come from someone else's report and end up running with the game, so they are never written unseen
+    if !yes {
+        println!();
+        println!("{}", format!("Launch options from {}'s report: {}", report.contributor.steam.nickname, launch_options).yellow());
+        println!("{}", "Check them, then run again with --yes to write them".truecolor(120, 120, 120));
+        return Ok(());
+    }
+
+    for change in changes {
+        let backup = write_with_backup(&change.path, vdf::to_string(&change.config).as_bytes())
+            .map_err(|error| format!("could not write {}: {}", change.path.display(), error))?;
+        println!("{}", format!("Backup written to {}", backup.display()).truecolor(120, 120, 120));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir;
+
+    const FIXTURE: &str = "tests/fixtures/steam";
+
+    fn launch_options(config: &vdf::Value, app_id: &str) -> Option<String> {
+        config.get_path(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", app_id, "LaunchOptions"])
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string())
+    }
+
+    #[test]
+    fn finds_user_config_dirs() {
+        let roots = [PathBuf::from(FIXTURE)];
+        let all = user_config_dirs(&roots, None);
+        assert_eq!(all, [
+            PathBuf::from(FIXTURE).join("userdata/12345/config"),
+            PathBuf::from(FIXTURE).join("userdata/67890/config"),
+        ]);
+        assert_eq!(user_config_dirs(&roots, Some("67890")), all[1..]);
+        assert!(user_config_dirs(&roots, Some("1")).is_empty());
+    }
+
+    #[test]
+    fn replaces_existing_launch_options() {
+        let path = PathBuf::from(FIXTURE).join("userdata/12345/config/localconfig.vdf");
+        let change = plan_launch_options(&path, 1091500, "DXVK_ASYNC=1 %command%").unwrap();
+        assert_eq!(change.old.as_deref(), Some("PROTON_ENABLE_NVAPI=1 %command%"));
+        assert_eq!(launch_options(&change.config, "1091500").as_deref(), Some("DXVK_ASYNC=1 %command%"));
+
+        // everything else is written back as it was
+        let written = vdf::parse(&vdf::to_string(&change.config)).unwrap();
+        assert_eq!(written, change.config);
+        assert_eq!(written.get_path(&["UserLocalConfigStore", "friends", "PersonaName"]).and_then(|value| value.as_str()), Some("fixture"));
+        assert_eq!(written.get_path(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", "1091500", "LastPlayed"]).and_then(|value| value.as_str()), Some("1700000000"));
+    }
+
+    #[test]
+    fn adds_launch_options_to_apps_without_them() {
+        let path = PathBuf::from(FIXTURE).join("userdata/12345/config/localconfig.vdf");
+        let change = plan_launch_options(&path, 292030, "%command% -dx11").unwrap();
+        assert_eq!(change.old, None);
+        assert_eq!(launch_options(&change.config, "292030").as_deref(), Some("%command% -dx11"));
+
+        let path = PathBuf::from(FIXTURE).join("userdata/67890/config/localconfig.vdf");
+        let change = plan_launch_options(&path, 292030, "%command% -dx11").unwrap();
+        assert_eq!(launch_options(&change.config, "292030").as_deref(), Some("%command% -dx11"));
+    }
+
+    #[test]
+    fn plans_nothing_when_one_config_is_broken() {
+        let dir = test_dir("broken-config");
+        fs::write(dir.join("localconfig.vdf"), "\"UserLocalConfigStore\"\n{\n").unwrap();
+        let config_dirs = [PathBuf::from(FIXTURE).join("userdata/12345/config"), dir.clone()];
+        let error = plan_all_launch_options(&config_dirs, 1091500, "%command%").err().unwrap();
+        assert!(error.contains(&dir.display().to_string()), "{}", error);
+        assert_eq!(plan_all_launch_options(&config_dirs[..1], 1091500, "%command%").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn keeps_every_backup() {
+        let dir = test_dir("backups");
+        let path = dir.join("localconfig.vdf");
+        fs::write(&path, "first").unwrap();
+
+        let first = write_with_backup(&path, b"second").unwrap();
+        let second = write_with_backup(&path, b"third").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(fs::read_to_string(first).unwrap(), "first");
+        assert_eq!(fs::read_to_string(second).unwrap(), "second");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "third");
+        // no temporary file is left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn detects_running_steam() {
+        let sysroot = test_dir("proc");
+        for (pid, comm) in [("1", "systemd\n"), ("42", "steamwebhelper\n")] {
+            fs::create_dir_all(sysroot.join("proc").join(pid)).unwrap();
+            fs::write(sysroot.join("proc").join(pid).join("comm"), comm).unwrap();
+        }
+        assert!(!is_steam_running(&sysroot));
+
+        fs::create_dir_all(sysroot.join("proc/1337")).unwrap();
+        fs::write(sysroot.join("proc/1337/comm"), "steam\n").unwrap();
+        assert!(is_steam_running(&sysroot));
+    }
+}
@@ -0,0 +1,210 @@
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use colored::{ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
+
+// ordered from worst to best so that comparisons read as "at least Gold"
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub(crate) enum Tier {
+    Pending,
+    Borked,
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    // tiers ProtonDB added after this was written, kept as they were sent
+    Unknown(String),
+}
+
+impl Tier {
+    pub const RATED: [Tier; 5] = [Tier::Platinum, Tier::Gold, Tier::Silver, Tier::Bronze, Tier::Borked];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Tier::Pending => "pending",
+            Tier::Borked => "borked",
+            Tier::Bronze => "bronze",
+            Tier::Silver => "silver",
+            Tier::Gold => "gold",
+            Tier::Platinum => "platinum",
+            Tier::Unknown(tier) => tier,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Tier::Unknown(_) => 0,
+            Tier::Pending => 1,
+            Tier::Borked => 2,
+            Tier::Bronze => 3,
+            Tier::Silver => 4,
+            Tier::Gold => 5,
+            Tier::Platinum => 6,
+        }
+    }
+
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Tier::Borked => (255, 0, 0),
+            Tier::Bronze => (205, 127, 50),
+            Tier::Silver => (166, 166, 166),
+            Tier::Gold => (207, 181, 59),
+            Tier::Platinum => (180, 199, 220),
+            Tier::Pending => (68, 68, 68),
+            Tier::Unknown(_) => (200, 200, 200),
+        }
+    }
+
+    pub fn colored(&self) -> ColoredString {
+        let (r, g, b) = self.rgb();
+        match self {
+            Tier::Pending => "Pending (unrated)".truecolor(r, g, b),
+            _ => self.to_string().truecolor(r, g, b),
+        }
+    }
+}
+
+impl FromStr for Tier {
+    type Err = Infallible;
+
+    fn from_str(tier: &str) -> Result<Self, Self::Err> {
+        Ok(match tier.to_lowercase().as_str() {
+            "pending" => Tier::Pending,
+            "borked" => Tier::Borked,
+            "bronze" => Tier::Bronze,
+            "silver" => Tier::Silver,
+            "gold" => Tier::Gold,
+            "platinum" => Tier::Platinum,
+            _ => Tier::Unknown(tier.to_string()),
+        })
+    }
+}
+
+impl From<String> for Tier {
+    fn from(tier: String) -> Self {
+        tier.parse().unwrap()
+    }
+}
+
+impl From<Tier> for String {
+    fn from(tier: Tier) -> Self {
+        tier.as_str().to_string()
+    }
+}
+
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tier::Pending => write!(f, "Pending"),
+            Tier::Unknown(tier) => write!(f, "{}", tier),
+            _ => {
+                let tier = self.as_str();
+                write!(f, "{}{}", tier[..1].to_uppercase(), &tier[1..])
+            }
+        }
+    }
+}
+
+impl Ord for Tier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank()).then_with(|| self.as_str().cmp(other.as_str()))
+    }
+}
+
+impl PartialOrd for Tier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub(crate) enum DeckStatus {
+    Unsupported,
+    Playable,
+    Verified,
+    Unknown(String),
+}
+
+impl DeckStatus {
+    // Algolia lists the status as an "oslist" entry such as "Steam Deck Verified"
+    pub fn from_oslist(oslist: &[String]) -> Option<DeckStatus> {
+        oslist.iter()
+            .find_map(|os| os.strip_prefix("Steam Deck"))
+            .map(|status| status.trim().parse().unwrap())
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            DeckStatus::Unsupported => "Unsupported",
+            DeckStatus::Playable => "Playable",
+            DeckStatus::Verified => "Verified",
+            DeckStatus::Unknown(status) => status,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            DeckStatus::Unknown(_) => 0,
+            DeckStatus::Unsupported => 1,
+            DeckStatus::Playable => 2,
+            DeckStatus::Verified => 3,
+        }
+    }
+
+    pub fn colored(&self) -> ColoredString {
+        match self {
+            DeckStatus::Verified => "Verified".green(),
+            DeckStatus::Playable => "Playable".yellow(),
+            DeckStatus::Unsupported => "Unsupported".red(),
+            DeckStatus::Unknown(status) if status.is_empty() => ColoredString::from("unknown"),
+            DeckStatus::Unknown(status) => ColoredString::from(status.as_str()),
+        }
+    }
+}
+
+impl FromStr for DeckStatus {
+    type Err = Infallible;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        Ok(match status.to_lowercase().as_str() {
+            "unsupported" => DeckStatus::Unsupported,
+            "playable" => DeckStatus::Playable,
+            "verified" => DeckStatus::Verified,
+            _ => DeckStatus::Unknown(status.to_string()),
+        })
+    }
+}
+
+impl From<String> for DeckStatus {
+    fn from(status: String) -> Self {
+        status.parse().unwrap()
+    }
+}
+
+impl From<DeckStatus> for String {
+    fn from(status: DeckStatus) -> Self {
+        status.as_str().to_string()
+    }
+}
+
+impl fmt::Display for DeckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Ord for DeckStatus {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank()).then_with(|| self.as_str().cmp(other.as_str()))
+    }
+}
+
+impl PartialOrd for DeckStatus {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
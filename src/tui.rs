@@ -0,0 +1,688 @@
+use std::io::{self, stdout};
+use chrono_humanize::HumanTime;
+use clap::ValueEnum;
+use crossterm::event::{read, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+use reqwest::Client;
+use crate::args::{Args, DeviceFilter};
+use crate::display_reports::{fetch_counts, fetch_reports_page, Outcome, Report, Reports};
+use crate::hardware::gpu_vendor;
+use crate::post_result::Game;
+use crate::recommend::proton_name;
+use crate::stats::{count_sorted, percentage};
+use crate::tier::{DeckStatus, Tier};
+use crate::utils::{fetch_summaries, is_native, search_hits, Counts, Summary};
+
+const PAGE_SIZE: usize = 10;
+const GAME_COUNT: u8 = 20;
+const GPU_VENDORS: [&str; 4] = ["NVIDIA", "AMD", "Intel", "Other"];
+const OUTCOMES: [Outcome; 4] = [Outcome::WorksOutOfTheBox, Outcome::WorksWithTinkering, Outcome::NotRecommended, Outcome::Borked];
+const GRAY: Color = Color::Rgb(120, 120, 120);
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Focus {
+    Games,
+    Reports,
+}
+
+// work the event loop has to do outside of the app state, so that the state itself stays free of IO
+#[derive(PartialEq, Debug)]
+pub(crate) enum Action {
+    None,
+    Quit,
+    Search(String),
+    LoadReports,
+    // every loaded report is shown, fetch the next page of the selected game
+    LoadMore,
+}
+
+pub(crate) struct App {
+    pub games: Vec<Game>,
+    pub summaries: Vec<Option<Summary>>,
+    pub selected: usize,
+    // reports of the selected game, None until they are loaded
+    pub reports: Option<Vec<Report>>,
+    // the game `reports` were loaded for, further pages are fetched for it
+    pub reports_app_id: Option<u32>,
+    // reports ProtonDB has for the game, of which loaded_pages pages are in `reports`
+    pub total: usize,
+    pub loaded_pages: u32,
+    pub device: DeviceFilter,
+    pub gpu_vendor: Option<&'static str>,
+    pub outcome: Option<Outcome>,
+    pub proton: Option<String>,
+    pub pages: usize,
+    pub scroll: u16,
+    pub focus: Focus,
+    // the query being typed, while in search mode
+    pub search: Option<String>,
+    pub status: String,
+}
+
+fn cycle<T: Clone + PartialEq>(options: &[T], current: &Option<T>) -> Option<T> {
+    match current {
+        None => options.first().cloned(),
+        Some(current) => options.iter().position(|option| option == current).and_then(|index| options.get(index + 1)).cloned(),
+    }
+}
+
+impl App {
+    pub fn new(device: DeviceFilter) -> App {
+        App {
+            games: Vec::new(),
+            summaries: Vec::new(),
+            selected: 0,
+            reports: None,
+            reports_app_id: None,
+            total: 0,
+            loaded_pages: 0,
+            device,
+            gpu_vendor: None,
+            outcome: None,
+            proton: None,
+            pages: 1,
+            scroll: 0,
+            focus: Focus::Games,
+            search: None,
+            status: String::new(),
+        }
+    }
+
+    pub fn set_games(&mut self, games: Vec<Game>, summaries: Vec<Option<Summary>>) {
+        self.games = games;
+        self.summaries = summaries;
+        self.selected = 0;
+        self.clear_reports();
+    }
+
+    // the first page of the selected game's reports
+    pub fn set_reports(&mut self, app_id: u32, reports: Reports) {
+        self.clear_reports();
+        self.total = (reports.total as usize).max(reports.reports.len());
+        self.loaded_pages = 1;
+        self.reports = Some(reports.reports);
+        self.reports_app_id = Some(app_id);
+    }
+
+    pub fn clear_reports(&mut self) {
+        self.total = 0;
+        self.loaded_pages = 0;
+        self.reports = None;
+        self.reports_app_id = None;
+        self.proton = None;
+        self.pages = 1;
+        self.scroll = 0;
+    }
+
+    fn select(&mut self, index: usize) {
+        let index = index.min(self.games.len().saturating_sub(1));
+        // the loaded reports belong to the previously selected game
+        if index != self.selected {
+            self.clear_reports();
+        }
+        self.selected = index;
+    }
+
+    pub fn add_page(&mut self, reports: Reports) {
+        self.reports.get_or_insert_with(Vec::new).extend(reports.reports);
+        self.loaded_pages += 1;
+        self.total = self.total.max(self.reports.as_ref().map_or(0, Vec::len));
+    }
+
+    pub fn has_more_pages(&self) -> bool {
+        self.reports.as_ref().is_some_and(|reports| reports.len() < self.total)
+    }
+
+    pub fn filtered_reports(&self) -> Vec<&Report> {
+        self.reports.iter().flatten()
+            .filter(|report| self.device.matches(&report.device))
+            .filter(|report| self.gpu_vendor.is_none_or(|vendor| gpu_vendor(&report.device.inferred.steam.gpu) == vendor))
+            .filter(|report| self.outcome.is_none_or(|outcome| report.outcome() == outcome))
+            .filter(|report| self.proton.as_ref().is_none_or(|proton| proton_name(&report.responses) == *proton))
+            .collect()
+    }
+
+    // Proton versions of the loaded reports, most reported first
+    fn proton_versions(&self) -> Vec<String> {
+        count_sorted(self.reports.iter().flatten().map(|report| proton_name(&report.responses)))
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.kind != KeyEventKind::Press { return Action::None }
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) { return Action::Quit }
+
+        if let Some(search) = &mut self.search {
+            match key.code {
+                KeyCode::Enter => {
+                    let query = search.clone();
+                    self.search = None;
+                    return if query.trim().is_empty() { Action::None } else { Action::Search(query) };
+                }
+                KeyCode::Esc => self.search = None,
+                KeyCode::Backspace => { search.pop(); }
+                KeyCode::Char(c) => search.push(c),
+                _ => {}
+            }
+            return Action::None;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('/') => self.search = Some(String::new()),
+            KeyCode::Tab => self.focus = if self.focus == Focus::Games { Focus::Reports } else { Focus::Games },
+            KeyCode::Up if self.focus == Focus::Games => self.select(self.selected.saturating_sub(1)),
+            KeyCode::Down if self.focus == Focus::Games => self.select(self.selected + 1),
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            // clamped to the content when drawn
+            KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::Enter if !self.games.is_empty() => {
+                self.focus = Focus::Reports;
+                return Action::LoadReports;
+            }
+            KeyCode::Char('d') => {
+                let devices = DeviceFilter::value_variants();
+                let index = devices.iter().position(|device| *device == self.device).unwrap_or(0);
+                self.device = devices[(index + 1) % devices.len()];
+            }
+            KeyCode::Char('g') => self.gpu_vendor = cycle(&GPU_VENDORS, &self.gpu_vendor),
+            KeyCode::Char('v') => self.outcome = cycle(&OUTCOMES, &self.outcome),
+            KeyCode::Char('p') => self.proton = cycle(&self.proton_versions(), &self.proton),
+            KeyCode::Char('n') => {
+                if self.pages * PAGE_SIZE < self.filtered_reports().len() {
+                    self.pages += 1;
+                } else if self.has_more_pages() {
+                    return Action::LoadMore;
+                }
+            }
+            KeyCode::Char('N') => self.pages = (self.pages - 1).max(1),
+            _ => return Action::None,
+        }
+
+        // filters change what the reports pane shows, so start from the top again
+        if matches!(key.code, KeyCode::Char('d' | 'g' | 'v' | 'p')) {
+            self.pages = 1;
+            self.scroll = 0;
+        }
+        Action::None
+    }
+}
+
+fn tier_span(tier: &Tier) -> Span<'static> {
+    let (r, g, b) = tier.rgb();
+    Span::styled(tier.to_string(), Style::default().fg(Color::Rgb(r, g, b)))
+}
+
+fn outcome_span(outcome: Outcome) -> Span<'static> {
+    let color = match outcome {
+        Outcome::WorksOutOfTheBox | Outcome::WorksWithTinkering => Color::Green,
+        Outcome::NotRecommended => Color::Yellow,
+        Outcome::Borked => Color::Red,
+    };
+    Span::styled(outcome.to_string(), Style::default().fg(color))
+}
+
+fn block(title: &str, focused: bool) -> Block<'_> {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if focused { block.border_style(Style::default().fg(Color::Cyan)) } else { block }
+}
+
+fn draw_games(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<Line> = app.games.iter().zip(&app.summaries)
+        .map(|(game, summary)| {
+            let tier = summary.as_ref().map(|summary| summary.tier.clone()).unwrap_or(Tier::Pending);
+            Line::from(vec![Span::raw(format!("{} ", game.name)), tier_span(&tier)])
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block("Games", app.focus == Focus::Games))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected((!app.games.is_empty()).then_some(app.selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_summary(frame: &mut Frame, app: &App, area: Rect) {
+    let mut lines = Vec::new();
+    if let Some(game) = app.games.get(app.selected) {
+        lines.push(Line::from(game.name.clone().bold()));
+        match &app.summaries[app.selected] {
+            Some(summary) => {
+                lines.push(Line::from(vec![
+                    "Rating: ".fg(GRAY), tier_span(&summary.tier),
+                    " (trending ".fg(GRAY), tier_span(&summary.trending_tier),
+                    ", best ".fg(GRAY), tier_span(&summary.best_reported_tier), ")".fg(GRAY),
+                ]));
+                lines.push(Line::from(vec![
+                    "Reports: ".fg(GRAY), Span::raw(format!("{} · {} confidence · score {:.2}", summary.total, summary.confidence, summary.score)),
+                ]));
+            }
+            None => lines.push(Line::from(vec!["Rating: ".fg(GRAY), tier_span(&Tier::Pending)])),
+        }
+        let steam_deck = DeckStatus::from_oslist(&game.oslist).map(|status| status.to_string()).unwrap_or("Unknown".to_string());
+        lines.push(Line::from(vec![
+            "Steam Deck: ".fg(GRAY), Span::raw(steam_deck),
+            if is_native(&game.oslist) { " · native Linux version".fg(Color::Green) } else { Span::raw("") },
+        ]));
+    }
+
+    let filters = format!("device {} · GPU {} · verdict {} · {}",
+        app.device,
+        app.gpu_vendor.unwrap_or("any"),
+        app.outcome.map(|outcome| outcome.to_string()).unwrap_or("any".to_string()),
+        app.proton.clone().unwrap_or("any Proton".to_string())
+    );
+    lines.push(Line::from(vec!["Filters: ".fg(GRAY), Span::raw(filters)]));
+
+    if app.reports.is_some() {
+        let reports = app.filtered_reports();
+        let works = reports.iter().filter(|report| report.outcome().works()).count();
+        lines.push(Line::from(vec![
+            "Matching: ".fg(GRAY),
+            Span::raw(format!("{} reports, {}% work", reports.len(), percentage(works, reports.len()))),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block("Summary", false)), area);
+}
+
+fn draw_reports(frame: &mut Frame, app: &mut App, area: Rect) {
+    let mut lines = Vec::new();
+    let title;
+    match &app.reports {
+        None => {
+            title = "Reports".to_string();
+            lines.push(Line::from("Press enter to load the reports of the selected game".fg(GRAY)));
+        }
+        Some(_) => {
+            let reports = app.filtered_reports();
+            let shown = reports.len().min(app.pages * PAGE_SIZE);
+            title = format!("Reports ({} of {})", shown, reports.len());
+            for report in reports.iter().take(shown) {
+                let timestamp = chrono::DateTime::from_timestamp(report.timestamp as i64, 0).unwrap();
+                let specs = &report.device.inferred.steam;
+                lines.push(Line::from(vec![
+                    outcome_span(report.outcome()),
+                    Span::raw(format!(" · {} · ", proton_name(&report.responses))),
+                    format!("{} · {}", report.contributor.steam.nickname, HumanTime::from(timestamp)).fg(GRAY),
+                ]));
+                lines.push(Line::from(format!("{} · {} · {}", specs.gpu, specs.gpu_driver, specs.os).fg(GRAY)));
+                let notes = [&report.responses.notes.verdict, &report.responses.concluding_notes];
+                for note in notes.into_iter().flatten().filter(|note| !note.is_empty()) {
+                    lines.push(Line::from(note.replace('\n', " ")));
+                }
+                lines.push(Line::from(""));
+            }
+            if shown < reports.len() || app.has_more_pages() {
+                lines.push(Line::from("Press n for more reports".fg(GRAY)));
+            }
+        }
+    }
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true });
+    // stop scrolling once the last line is at the bottom of the pane
+    let content = area.inner(&Margin::new(1, 1));
+    let max_scroll = paragraph.line_count(content.width).saturating_sub(content.height as usize);
+    app.scroll = app.scroll.min(max_scroll.try_into().unwrap_or(u16::MAX));
+
+    let paragraph = paragraph
+        .block(block(&title, app.focus == Focus::Reports))
+        .scroll((app.scroll, 0));
+    frame.render_widget(paragraph, area);
+}
+
+pub(crate) fn draw(frame: &mut Frame, app: &mut App) {
+    let [main, footer] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.size());
+    let [games, right] = Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)]).areas(main);
+    let [summary, reports] = Layout::vertical([Constraint::Length(8), Constraint::Min(0)]).areas(right);
+
+    draw_games(frame, app, games);
+    draw_summary(frame, app, summary);
+    draw_reports(frame, app, reports);
+
+    let footer_text = match &app.search {
+        Some(search) => Line::from(vec!["Search: ".fg(Color::Cyan), Span::raw(search.clone())]),
+        None if !app.status.is_empty() => Line::from(app.status.clone().fg(GRAY)),
+        None => Line::from("/ search · enter load · tab switch pane · d device · g GPU · v verdict · p Proton · n/N page · q quit".fg(GRAY)),
+    };
+    frame.render_widget(Paragraph::new(footer_text), footer);
+}
+
+async fn search(client: &Client, app: &mut App, query: &str) {
+    let games = search_hits(client, query, GAME_COUNT).await;
+    let steam_ids: Vec<String> = games.iter().map(|game| game.object_id.clone()).collect();
+    let summaries = fetch_summaries(client, &steam_ids).await;
+    app.status = if games.is_empty() { format!("No games found for \"{}\"", query) } else { String::new() };
+    app.set_games(games, summaries);
+}
+
+fn selected_app_id(app: &App) -> u32 {
+    app.games[app.selected].object_id.parse().unwrap()
+}
+
+// reads key presses from `next_event` until the user quits or reading fails
+async fn run<B: Backend>(terminal: &mut Terminal<B>, client: &Client, app: &mut App, query: &str, mut next_event: impl FnMut() -> io::Result<Event>) {
+    let mut counts: Option<Counts> = None;
+
+    if !query.is_empty() {
+        app.status = format!("Searching for \"{}\"…", query);
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        search(client, app, query).await;
+    }
+
+    loop {
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        let key = match next_event() {
+            Ok(Event::Key(key)) => key,
+            Ok(_) => continue,
+            Err(_) => return,
+        };
+
+        match app.handle_key(key) {
+            Action::None => {}
+            Action::Quit => return,
+            Action::Search(query) => {
+                app.status = format!("Searching for \"{}\"…", query);
+                terminal.draw(|frame| draw(frame, app)).unwrap();
+                search(client, app, &query).await;
+            }
+            Action::LoadReports => {
+                app.status = "Loading reports…".to_string();
+                terminal.draw(|frame| draw(frame, app)).unwrap();
+                if counts.is_none() {
+                    counts = Some(fetch_counts(client).await);
+                }
+                let app_id = selected_app_id(app);
+                let reports = fetch_reports_page(client, app_id, counts.as_ref().unwrap(), 1).await;
+                app.status = if reports.is_none() { "No reports for this game".to_string() } else { String::new() };
+                app.set_reports(app_id, reports.unwrap_or_default());
+            }
+            Action::LoadMore => {
+                // only asked for while reports are loaded
+                let Some(app_id) = app.reports_app_id else { continue };
+                app.status = format!("Loading page {}…", app.loaded_pages + 1);
+                terminal.draw(|frame| draw(frame, app)).unwrap();
+                // counts were fetched with the first page
+                let reports = fetch_reports_page(client, app_id, counts.as_ref().unwrap(), app.loaded_pages + 1).await;
+                match reports.filter(|reports| !reports.reports.is_empty()) {
+                    Some(reports) => {
+                        app.status = String::new();
+                        app.add_page(reports);
+                        app.pages += 1;
+                    }
+                    None => {
+                        app.status = "No more reports".to_string();
+                        app.total = app.reports.as_ref().map_or(0, Vec::len);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(stdout(), LeaveAlternateScreen);
+}
+
+// leaves the alternate screen and raw mode however run returns, including by a panic
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<TerminalGuard> {
+        enable_raw_mode()?;
+        let guard = TerminalGuard;
+        execute!(stdout(), EnterAlternateScreen)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+pub(crate) async fn run_tui(args: &Args, client: &Client, query: &str) {
+    let mut app = App::new(args.device_filter());
+
+    // the panic message would otherwise be printed to the alternate screen and lost with it
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        hook(info);
+    }));
+
+    let _guard = TerminalGuard::enter().unwrap();
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout())).unwrap();
+    run(&mut terminal, client, &mut app, query, read).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use crate::display_reports::parse_reports;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn game(name: &str, app_id: &str) -> Game {
+        Game { name: name.to_string(), object_id: app_id.to_string(), oslist: vec!["Windows".to_string(), "Steam Deck Verified".to_string()] }
+    }
+
+    fn summary() -> Summary {
+        serde_json::from_str(r#"{"bestReportedTier": "platinum", "confidence": "strong", "score": 0.76, "tier": "gold", "total": 30, "trendingTier": "platinum"}"#).unwrap()
+    }
+
+    // a game with the first page of its reports loaded: 12 of 30
+    fn app() -> App {
+        let mut app = App::new(DeviceFilter::All);
+        app.set_games(vec![game("Cyberpunk 2077", "1091500"), game("The Witcher 3: Wild Hunt", "292030")], vec![Some(summary()), None]);
+        app.set_reports(1091500, parse_reports(include_str!("../tests/fixtures/reports.json"), false).unwrap());
+        app
+    }
+
+    fn render(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer.get(x, y).symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn searches_from_the_footer() {
+        let mut app = app();
+        assert_eq!(app.handle_key(key(KeyCode::Char('/'))), Action::None);
+        for c in "eldn".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+        app.handle_key(key(KeyCode::Backspace));
+        app.handle_key(key(KeyCode::Char('n')));
+        // keys are typed into the query instead of acting as shortcuts
+        assert_eq!(app.search.as_deref(), Some("eldn"));
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), Action::Search("eldn".to_string()));
+        assert_eq!(app.search, None);
+
+        app.handle_key(key(KeyCode::Char('/')));
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), Action::None);
+        app.handle_key(key(KeyCode::Char('/')));
+        app.handle_key(key(KeyCode::Char('q')));
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), Action::None);
+        assert_eq!(app.handle_key(key(KeyCode::Char('q'))), Action::Quit);
+        assert_eq!(app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)), Action::Quit);
+    }
+
+    #[test]
+    fn moves_between_games_and_loads_reports() {
+        let mut app = app();
+        app.handle_key(key(KeyCode::Up));
+        assert_eq!(app.selected, 0);
+        app.handle_key(key(KeyCode::Down));
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(app.selected, 1);
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), Action::LoadReports);
+        assert!(app.focus == Focus::Reports);
+        // with the reports pane focused the arrows scroll instead
+        app.handle_key(key(KeyCode::Up));
+        assert_eq!(app.selected, 1);
+    }
+
+    #[test]
+    fn filters_reports() {
+        let mut app = app();
+        assert_eq!(app.filtered_reports().len(), 12);
+
+        app.handle_key(key(KeyCode::Char('d')));
+        assert!(app.device == DeviceFilter::Pc);
+        assert_eq!(app.filtered_reports().len(), 8);
+        app.handle_key(key(KeyCode::Char('d')));
+        assert_eq!(app.filtered_reports().len(), 3);
+        app.handle_key(key(KeyCode::Char('d')));
+        app.handle_key(key(KeyCode::Char('d')));
+        assert!(app.device == DeviceFilter::All);
+
+        app.handle_key(key(KeyCode::Char('g')));
+        assert_eq!(app.gpu_vendor, Some("NVIDIA"));
+        assert_eq!(app.filtered_reports().len(), 3);
+        app.handle_key(key(KeyCode::Char('g')));
+        assert_eq!(app.filtered_reports().len(), 6);
+        app.gpu_vendor = None;
+
+        app.handle_key(key(KeyCode::Char('v')));
+        assert_eq!(app.filtered_reports().len(), 7);
+        app.handle_key(key(KeyCode::Char('v')));
+        assert_eq!(app.filtered_reports().len(), 3);
+        app.outcome = None;
+
+        app.handle_key(key(KeyCode::Char('p')));
+        let proton = app.proton.clone().unwrap();
+        assert!(app.filtered_reports().iter().all(|report| proton_name(&report.responses) == proton));
+    }
+
+    #[test]
+    fn pages_through_loaded_reports_then_loads_more() {
+        let mut app = app();
+        assert!(app.has_more_pages());
+        assert_eq!(app.handle_key(key(KeyCode::Char('n'))), Action::None);
+        assert_eq!(app.pages, 2);
+        // all 12 loaded reports are shown, the next 18 are on ProtonDB's next page
+        assert_eq!(app.handle_key(key(KeyCode::Char('n'))), Action::LoadMore);
+        app.handle_key(key(KeyCode::Char('N')));
+        app.handle_key(key(KeyCode::Char('N')));
+        assert_eq!(app.pages, 1);
+
+        let mut page = parse_reports(include_str!("../tests/fixtures/reports.json"), false).unwrap();
+        page.reports.truncate(3);
+        app.add_page(page);
+        assert_eq!((app.reports.as_ref().unwrap().len(), app.loaded_pages), (15, 2));
+
+        // once everything is loaded, n stops asking for more
+        app.total = 15;
+        app.pages = 2;
+        assert_eq!(app.handle_key(key(KeyCode::Char('n'))), Action::None);
+    }
+
+    #[test]
+    fn draws_every_pane() {
+        let mut app = app();
+        let screen = render(&mut app);
+        assert!(screen.contains("Cyberpunk 2077 Gold"), "{}", screen);
+        assert!(screen.contains("Rating: Gold (trending Platinum, best Platinum)"), "{}", screen);
+        assert!(screen.contains("Steam Deck: Verified"), "{}", screen);
+        assert!(screen.contains("Reports (10 of 12)"), "{}", screen);
+        assert!(screen.contains("Matching: 12 reports, 83% work"), "{}", screen);
+        assert!(screen.contains("/ search"), "{}", screen);
+
+        app.handle_key(key(KeyCode::Char('/')));
+        app.handle_key(key(KeyCode::Char('x')));
+        assert!(render(&mut app).contains("Search: x"));
+    }
+
+    #[test]
+    fn clamps_scrolling_to_the_reports() {
+        let mut app = app();
+        app.focus = Focus::Reports;
+        for _ in 0..10_000 {
+            app.handle_key(key(KeyCode::PageDown));
+        }
+        render(&mut app);
+        let bottom = app.scroll;
+        assert!(bottom > 0 && bottom < 100, "{}", bottom);
+        app.handle_key(key(KeyCode::Up));
+        assert_eq!(app.scroll, bottom - 1);
+
+        app.clear_reports();
+        app.scroll = u16::MAX;
+        render(&mut app);
+        assert_eq!(app.scroll, 0);
+    }
+
+    #[tokio::test]
+    async fn runs_headless_until_quit() {
+        let mut app = app();
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let mut events = vec![Event::Key(key(KeyCode::Char('q'))), Event::Key(key(KeyCode::Char('v'))), Event::Key(key(KeyCode::Char('d')))];
+        run(&mut terminal, &Client::new(), &mut app, "", || Ok(events.pop().unwrap())).await;
+        assert!(app.device == DeviceFilter::Pc);
+        assert_eq!(app.outcome, Some(Outcome::WorksOutOfTheBox));
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn selecting_another_game_drops_its_reports() {
+        let mut app = app();
+        assert_eq!(app.reports_app_id, Some(1091500));
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(app.selected, 1);
+        assert!(app.reports.is_none() && app.reports_app_id.is_none());
+        assert!(!app.has_more_pages());
+
+        let screen = render(&mut app);
+        assert!(!screen.contains("Matching:"), "{}", screen);
+        assert!(screen.contains("Press enter to load the reports of the selected game"), "{}", screen);
+
+        // staying on the same game keeps them
+        let mut first = self::app();
+        first.handle_key(key(KeyCode::Up));
+        assert_eq!(first.reports_app_id, Some(1091500));
+    }
+
+    #[tokio::test]
+    async fn does_not_load_more_reports_for_another_game() {
+        let mut app = app();
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        // moving to the next game, then paging: a LoadMore would need a network request and page counts that were never fetched
+        let mut events = vec![Event::Key(key(KeyCode::Char('q'))), Event::Key(key(KeyCode::Char('n'))), Event::Key(key(KeyCode::Char('n'))), Event::Key(key(KeyCode::Down))];
+        run(&mut terminal, &Client::new(), &mut app, "", || Ok(events.pop().unwrap())).await;
+        assert!(events.is_empty());
+        assert_eq!(app.selected, 1);
+        assert!(app.reports.is_none());
+    }
+
+    #[tokio::test]
+    async fn stops_when_reading_events_fails() {
+        let mut app = app();
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let mut reads = 0;
+        run(&mut terminal, &Client::new(), &mut app, "", || {
+            reads += 1;
+            Err(io::Error::other("no terminal"))
+        }).await;
+        assert_eq!(reads, 1);
+    }
+}
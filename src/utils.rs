@@ -1,24 +1,119 @@
-use colored::{ColoredString, Colorize};
+use colored::Colorize;
 use futures::future::join_all;
 use image::DynamicImage;
-use reqwest::Client;
+use reqwest::{Client, ClientBuilder};
+use reqwest::header::HeaderMap;
 use serde::{Deserialize, Serialize};
 use viuer::Config;
+use crate::args::Prefer;
+use crate::post_body::Body;
+use crate::post_result::{Game, PostResult};
+use crate::tier::Tier;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub(crate) struct Summary {
     #[serde(rename = "bestReportedTier")]
-    pub best_reported_tier: String,
+    pub best_reported_tier: Tier,
     pub confidence: String,
     pub score: f64,
-    pub tier: String,
+    pub tier: Tier,
     pub total: u32,
     #[serde(rename = "trendingTier")]
-    pub trending_tier: String,
+    pub trending_tier: Tier,
 }
 
-pub(crate) async fn fetch_summaries(client: &Client, steam_ids: &Vec<String>) -> Vec<Option<Summary>> {
-    let futures = steam_ids.into_iter().map(|steam_id| {
+pub(crate) fn build_client() -> Client {
+    ClientBuilder::new()
+        .default_headers(
+            HeaderMap::from_iter(
+                vec![
+                    ("x-algolia-api-key", "9ba0e69fb2974316cdaec8f5f257088f"),
+                    ("x-algolia-application-id", "94HE6YATEI"),
+                    ("Referer", "https://www.protondb.com")
+                ].into_iter().map(|(k, v)| (k.parse().unwrap(), v.parse().unwrap()))
+            )
+        ).build().unwrap()
+}
+
+pub(crate) fn join_query(query: &[String]) -> String {
+    let query = query.iter()
+        .flat_map(|arg| arg.split_whitespace())
+        .collect::<Vec<_>>()
+        .join(" ");
+    normalize_query(&query)
+}
+
+// links to a game are replaced by its app ID, anything else is searched for as it is
+pub(crate) fn normalize_query(query: &str) -> String {
+    app_id_from_link(query).map(|app_id| app_id.to_string()).unwrap_or(query.to_string())
+}
+
+// store.steampowered.com/app/<id>/..., steamdb.info/app/<id>/, protondb.com/app/<id> and steam://run/<id>
+pub(crate) fn app_id_from_link(link: &str) -> Option<u32> {
+    const HOSTS: [&str; 3] = ["store.steampowered.com", "steamdb.info", "protondb.com"];
+
+    let (scheme, rest) = link.trim().split_once("://").unwrap_or(("https", link.trim()));
+    let path = match scheme.to_ascii_lowercase().as_str() {
+        "steam" => rest.strip_prefix("run/").or(rest.strip_prefix("rungameid/"))?,
+        "http" | "https" => {
+            let (host, path) = rest.split_once('/')?;
+            let host = host.to_ascii_lowercase();
+            if !HOSTS.contains(&host.strip_prefix("www.").unwrap_or(&host)) { return None }
+            path.strip_prefix("app/")?
+        }
+        _ => return None,
+    };
+    path.split(['/', '?', '#']).next()?.parse().ok()
+}
+
+pub(crate) async fn search_games(client: &Client, query: &str, count: u8) -> Vec<Game> {
+    let body = Body {
+        query: query.to_string(),
+        facet_filters: vec![vec!["appType:Game"]],
+        hits_per_page: count,
+        attributes_to_retrieve: vec!["name", "objectID", "oslist"],
+        page: 0
+    };
+
+    client
+        .post("https://94he6yatei-dsn.algolia.net/1/indexes/steamdb/query")
+        .body(serde_json::to_string(&body).unwrap())
+        .send()
+        .await.unwrap().json::<PostResult>().await.unwrap()
+        .hits
+}
+
+// requests in flight at once when fetching many games or report pages
+pub(crate) const CONCURRENT_REQUESTS: usize = 8;
+
+// Algolia doesn't always rank the game with a searched app ID first, so ID searches look at this many hits
+const ID_SEARCH_COUNT: u8 = 20;
+
+// search hits to list, with the game whose app ID was searched for (or linked to) first
+pub(crate) async fn search_hits(client: &Client, query: &str, count: u8) -> Vec<Game> {
+    let query = normalize_query(query);
+    if !is_query_id(&query) {
+        return search_games(client, &query, count).await;
+    }
+
+    let mut hits = search_games(client, &query, count.max(ID_SEARCH_COUNT)).await;
+    if let Some(index) = hits.iter().position(|hit| hit.object_id == query) {
+        let hit = hits.remove(index);
+        hits.insert(0, hit);
+    }
+    hits.truncate(count as usize);
+    hits
+}
+
+// the one game a query is about: the game with that app ID for IDs and links, otherwise the best match for the name
+pub(crate) async fn resolve_game(client: &Client, query: &str) -> Option<Game> {
+    let query = normalize_query(query);
+    search_hits(client, &query, 1).await.into_iter().next()
+        .filter(|hit| !is_query_id(&query) || hit.object_id == query)
+}
+
+pub(crate) async fn fetch_summaries(client: &Client, steam_ids: &[String]) -> Vec<Option<Summary>> {
+    let futures = steam_ids.iter().map(|steam_id| {
         let client = client.clone();
         async move {
             let summary = client.get(format!("https://www.protondb.com/api/v1/reports/summaries/{}.json", steam_id))
@@ -30,39 +125,77 @@ pub(crate) async fn fetch_summaries(client: &Client, steam_ids: &Vec<String>) ->
     join_all(futures).await
 }
 
-pub(crate) fn get_colored_tier(tier: &String, oslist: &Vec<String>) -> ColoredString {
-    if oslist.contains(&"Linux".to_string()) { return "Native".truecolor(0, 255, 0) }
-    match tier.as_str() {
-        "borked" => "Borked".truecolor(255, 0, 0),
-        "bronze" => "Bronze".truecolor(205, 127, 50),
-        "silver" => "Silver".truecolor(166, 166, 166),
-        "gold" => "Gold".truecolor(207, 181, 59),
-        "platinum" => "Platinum".truecolor(180, 199, 220),
-        "pending" => "Pending (unrated)".truecolor(68, 68, 68),
-        _ => "unknown".truecolor(200, 200, 200)
+pub(crate) fn is_native(oslist: &[String]) -> bool {
+    oslist.iter().any(|os| os == "Linux")
+}
+
+// games with a native Linux version show both ratings, the preferred one first
+pub(crate) fn get_colored_tier(tier: &Tier, oslist: &[String], prefer: Prefer) -> String {
+    let native = "Native".truecolor(0, 255, 0);
+    if !is_native(oslist) { return tier.colored().to_string() }
+    match prefer {
+        Prefer::Native if *tier == Tier::Pending => native.to_string(),
+        Prefer::Native => format!("{} {}", native, format!("(Proton: {})", tier.colored()).truecolor(120, 120, 120)),
+        Prefer::Proton => format!("{} {}", tier.colored(), format!("({} available)", native).truecolor(120, 120, 120)),
+    }
+}
+
+// length of a string as displayed, without ANSI color codes
+pub(crate) fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for char in text.chars() {
+        match (in_escape, char) {
+            (false, '\x1B') => in_escape = true,
+            (true, 'm') => in_escape = false,
+            (true, _) => {}
+            (false, _) => width += 1,
+        }
     }
+    width
 }
 
-pub(crate) fn get_colored_steam_deck_status(status: &String) -> ColoredString {
-    let status = status.split_whitespace().last().unwrap();
-    match status {
-        "Verified" => "Verified".green(),
-        "Playable" => "Playable".yellow(),
-        "Unsupported" => "Unsupported".red(),
-        _ => ColoredString::from("unknown")
+// "(trending Platinum, best Platinum) · 312 reports · strong confidence", leaving out tiers equal to the current one
+pub(crate) fn format_summary_details(summary: &Summary) -> String {
+    let mut tiers = Vec::new();
+    if summary.trending_tier != summary.tier {
+        tiers.push(format!("trending {}", summary.trending_tier.colored()));
+    }
+    if summary.best_reported_tier != summary.tier {
+        tiers.push(format!("best {}", summary.best_reported_tier.colored()));
+    }
+
+    let mut details = Vec::new();
+    if !tiers.is_empty() {
+        details.push(format!("({})", tiers.join(", ")));
     }
+    details.push(format!("· {} report{} · {} confidence",
+        summary.total,
+        if summary.total == 1 { "" } else { "s" },
+        summary.confidence
+    ).truecolor(120, 120, 120).to_string());
+    details.join(" ")
+}
+
+// score is between 0 and 1
+pub(crate) fn score_bar(score: f64, width: usize) -> String {
+    let filled = ((score.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+    format!("{}{} {:.2}",
+        "█".repeat(filled).truecolor(180, 199, 220),
+        "░".repeat(width - filled).truecolor(68, 68, 68),
+        score
+    )
 }
 
-pub(crate) async fn fetch_images(client: &Client, steam_ids: &Vec<String>) -> Vec<DynamicImage> {
-    let futures = steam_ids.into_iter().map(|steam_id| {
+pub(crate) async fn fetch_images(client: &Client, steam_ids: &[String]) -> Vec<DynamicImage> {
+    let futures = steam_ids.iter().map(|steam_id| {
         let client = client.clone();
         async move {
             let image = client.get(format!("https://steamcdn-a.akamaihd.net/steam/apps/{}/header.jpg", steam_id))
                 .send().await.unwrap()
                 .bytes().await.unwrap().to_vec();
             let image = image::load_from_memory(&image).unwrap();
-            let image = DynamicImage::ImageRgba8(image.to_rgba8());
-            image
+            DynamicImage::ImageRgba8(image.to_rgba8())
         }
     });
     join_all(futures).await
@@ -90,12 +223,13 @@ fn get_protondb_id(hash: &str) -> u32 {
     (hash.to_owned() + "m")
         .chars()
         .fold(0, |acc: i32, char| (acc << 5).wrapping_sub(acc) + char as i32)
-        .abs() as u32
+        .unsigned_abs()
 }
 
-pub(crate) fn calculate_protondb_id(steam_id: u32, number_of_reports: u32, counts_timestamp: u32) -> u32 {
+// reports are split into pages, the first page is 1
+pub(crate) fn calculate_protondb_id(steam_id: u32, number_of_reports: u32, counts_timestamp: u32, page: u32) -> u32 {
     let hash1 = get_hash(steam_id, number_of_reports, counts_timestamp);
-    let hash2 = get_hash(1, steam_id, counts_timestamp);
+    let hash2 = get_hash(page, steam_id, counts_timestamp);
     let hash3 = format!("p{}*vRT{}undefined", hash1, hash2);
     get_protondb_id(&hash3)
 }
@@ -107,5 +241,73 @@ pub(crate) struct Counts {
 }
 
 pub(crate) fn is_query_id(query: &str) -> bool {
-    query.chars().all(char::is_numeric)
+    !query.is_empty() && query.chars().all(|c| c.is_ascii_digit())
+}
+
+// an empty directory of its own for a test that writes files
+#[cfg(test)]
+pub(crate) fn test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("protondb-cli-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_app_ids_from_links() {
+        let links = [
+            "https://store.steampowered.com/app/1091500/Cyberpunk_2077/",
+            "https://store.steampowered.com/app/1091500?snr=1_4_4__129_1",
+            "store.steampowered.com/app/1091500",
+            "HTTPS://Store.SteamPowered.com/app/1091500/",
+            "http://www.steamdb.info/app/1091500/charts/",
+            "https://steamdb.info/app/1091500#section",
+            "https://www.protondb.com/app/1091500",
+            "https://ProtonDB.com/app/1091500",
+            "steam://run/1091500",
+            "STEAM://rungameid/1091500",
+            "  steam://run/1091500/  ",
+        ];
+        for link in links {
+            assert_eq!(app_id_from_link(link), Some(1091500), "{}", link);
+        }
+    }
+
+    #[test]
+    fn ignores_other_links_and_names() {
+        let queries = [
+            "https://example.com/app/1091500",
+            "https://store.steampowered.com.evil.example/app/1091500",
+            "https://store.steampowered.com/sub/1091500",
+            "https://store.steampowered.com/app/",
+            "https://store.steampowered.com/app/cyberpunk",
+            "ftp://steamdb.info/app/1091500",
+            "steam://open/games",
+            "Cyberpunk 2077",
+            "1091500",
+        ];
+        for query in queries {
+            assert_eq!(app_id_from_link(query), None, "{}", query);
+        }
+    }
+
+    #[test]
+    fn normalizes_queries() {
+        assert_eq!(normalize_query("https://store.steampowered.com/app/292030/The_Witcher_3_Wild_Hunt/"), "292030");
+        assert_eq!(normalize_query("witcher 3"), "witcher 3");
+        assert_eq!(join_query(&["the".to_string(), " witcher  3 ".to_string()]), "the witcher 3");
+        assert_eq!(join_query(&["steam://run/292030".to_string()]), "292030");
+    }
+
+    #[test]
+    fn recognizes_app_ids() {
+        assert!(is_query_id("292030"));
+        assert!(!is_query_id(""));
+        assert!(!is_query_id("witcher 3"));
+        assert!(!is_query_id("٣"));
+    }
 }
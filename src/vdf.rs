@@ -0,0 +1,592 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+// Valve's KeyValues format, used by libraryfolders.vdf, appmanifest_*.acf, config.vdf and friends,
+// and its binary variant used by shortcuts.vdf and appinfo.vdf.
+// Numbers only exist in the binary format, the text format stores everything as strings.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    String(String),
+    Int32(i32),
+    Float32(f32),
+    UInt64(u64),
+    Int64(i64),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    // keys are case-insensitive in Steam's own files ("LaunchOptions" vs "launchoptions")
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn get_path(&self, path: &[&str]) -> Option<&Value> {
+        path.iter().try_fold(self, |value, key| value.get(key))
+    }
+
+    // sets a nested value, creating missing objects along the way and reusing existing keys regardless of case
+    pub fn set_path(&mut self, path: &[&str], value: Value) {
+        let Some((key, rest)) = path.split_first() else {
+            *self = value;
+            return;
+        };
+        if !matches!(self, Value::Object(_)) {
+            *self = Value::Object(Vec::new());
+        }
+        let Value::Object(entries) = self else { unreachable!() };
+        let index = match entries.iter().position(|(k, _)| k.eq_ignore_ascii_case(key)) {
+            Some(index) => index,
+            None => {
+                entries.push((key.to_string(), Value::Object(Vec::new())));
+                entries.len() - 1
+            }
+        };
+        entries[index].1.set_path(rest, value);
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn entries(&self) -> &[(String, Value)] {
+        match self {
+            Value::Object(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    fn to_text(&self) -> String {
+        match self {
+            Value::String(string) => string.clone(),
+            Value::Int32(int) => int.to_string(),
+            Value::Float32(float) => float.to_string(),
+            Value::UInt64(int) => int.to_string(),
+            Value::Int64(int) => int.to_string(),
+            Value::Object(_) => String::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum ParseError {
+    Text { line: usize, message: String },
+    Binary { offset: usize, message: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Text { line, message } => write!(f, "line {}: {}", line, message),
+            ParseError::Binary { offset, message } => write!(f, "offset {}: {}", offset, message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(PartialEq)]
+enum Token {
+    String(String),
+    Open,
+    Close,
+}
+
+struct Tokenizer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl Tokenizer<'_> {
+    fn error(&self, message: &str) -> ParseError {
+        ParseError::Text { line: self.line, message: message.to_string() }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(&char) = self.chars.peek() {
+            if char == '\n' {
+                self.line += 1;
+                self.chars.next();
+            } else if char.is_whitespace() {
+                self.chars.next();
+            } else if char == '/' {
+                // "//" comments run to the end of the line
+                let mut lookahead = self.chars.clone();
+                lookahead.next();
+                if lookahead.peek() != Some(&'/') { break }
+                while self.chars.peek().is_some_and(|&c| c != '\n') {
+                    self.chars.next();
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String, ParseError> {
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some(char) => string.push(char),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(char) => {
+                    if char == '\n' { self.line += 1 }
+                    string.push(char);
+                }
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<Token>, ParseError> {
+        self.skip_whitespace_and_comments();
+        match self.chars.peek() {
+            None => Ok(None),
+            Some('{') => {
+                self.chars.next();
+                Ok(Some(Token::Open))
+            }
+            Some('}') => {
+                self.chars.next();
+                Ok(Some(Token::Close))
+            }
+            Some('"') => {
+                self.chars.next();
+                Ok(Some(Token::String(self.quoted()?)))
+            }
+            Some('[') => {
+                // platform conditionals like [$WIN32] are ignored
+                while self.chars.next().is_some_and(|c| c != ']') {}
+                self.next()
+            }
+            Some(_) => {
+                let mut string = String::new();
+                while let Some(&char) = self.chars.peek() {
+                    if char.is_whitespace() || matches!(char, '{' | '}' | '"') { break }
+                    string.push(char);
+                    self.chars.next();
+                }
+                Ok(Some(Token::String(string)))
+            }
+        }
+    }
+}
+
+fn parse_entries(tokenizer: &mut Tokenizer, nested: bool) -> Result<Vec<(String, Value)>, ParseError> {
+    let mut entries = Vec::new();
+    loop {
+        let key = match tokenizer.next()? {
+            Some(Token::String(key)) => key,
+            Some(Token::Close) if nested => return Ok(entries),
+            None if !nested => return Ok(entries),
+            Some(Token::Close) => return Err(tokenizer.error("unexpected '}'")),
+            Some(Token::Open) => return Err(tokenizer.error("expected a key, found '{'")),
+            None => return Err(tokenizer.error("unexpected end of file, expected '}'")),
+        };
+        let value = match tokenizer.next()? {
+            Some(Token::String(value)) => Value::String(value),
+            Some(Token::Open) => Value::Object(parse_entries(tokenizer, true)?),
+            Some(Token::Close) => return Err(tokenizer.error(&format!("expected a value for \"{}\", found '}}'", key))),
+            None => return Err(tokenizer.error(&format!("expected a value for \"{}\"", key))),
+        };
+        entries.push((key, value));
+    }
+}
+
+// parses a whole file into an object holding its top-level entries
+pub(crate) fn parse(text: &str) -> Result<Value, ParseError> {
+    let text = text.trim_start_matches('\u{feff}');
+    let mut tokenizer = Tokenizer { chars: text.chars().peekable(), line: 1 };
+    Ok(Value::Object(parse_entries(&mut tokenizer, false)?))
+}
+
+fn escape(string: &str) -> String {
+    string.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\t', "\\t")
+}
+
+fn write_entries(entries: &[(String, Value)], depth: usize, output: &mut String) {
+    let indent = "\t".repeat(depth);
+    for (key, value) in entries {
+        match value {
+            Value::Object(children) => {
+                output.push_str(&format!("{}\"{}\"\n{}{{\n", indent, escape(key), indent));
+                write_entries(children, depth + 1, output);
+                output.push_str(&format!("{}}}\n", indent));
+            }
+            value => output.push_str(&format!("{}\"{}\"\t\t\"{}\"\n", indent, escape(key), escape(&value.to_text()))),
+        }
+    }
+}
+
+// writes the top-level entries of an object in the same layout Steam uses.
+// Comments and [$PLATFORM] conditionals aren't kept by the parser, so they are lost when a parsed file is written back;
+// Steam's own config files contain neither.
+pub(crate) fn to_string(value: &Value) -> String {
+    let mut output = String::new();
+    write_entries(value.entries(), 0, &mut output);
+    output
+}
+
+const TYPE_OBJECT: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT32: u8 = 0x02;
+const TYPE_FLOAT32: u8 = 0x03;
+const TYPE_UINT64: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+const TYPE_INT64: u8 = 0x0A;
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn error(&self, message: &str) -> ParseError {
+        ParseError::Binary { offset: self.offset, message: message.to_string() }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], ParseError> {
+        let bytes = self.offset.checked_add(length)
+            .and_then(|end| self.bytes.get(self.offset..end))
+            .ok_or(self.error("unexpected end of file"))?;
+        self.offset += length;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, ParseError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, ParseError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, ParseError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        let length = self.bytes.get(self.offset..).unwrap_or_default().iter().position(|&byte| byte == 0).ok_or(self.error("unterminated string"))?;
+        let string = String::from_utf8_lossy(self.take(length)?).to_string();
+        self.offset += 1;
+        Ok(string)
+    }
+}
+
+// appinfo.vdf v29 replaces keys with indices into a string table at the end of the file
+fn parse_binary_entries(reader: &mut ByteReader, string_table: Option<&[String]>) -> Result<Vec<(String, Value)>, ParseError> {
+    let mut entries = Vec::new();
+    loop {
+        if reader.offset == reader.bytes.len() && string_table.is_none() {
+            return Ok(entries);
+        }
+        let value_type = reader.u8()?;
+        if value_type == TYPE_END {
+            return Ok(entries);
+        }
+
+        let key = match string_table {
+            Some(string_table) => {
+                let index = reader.u32()? as usize;
+                string_table.get(index).ok_or(reader.error("string table index out of range"))?.clone()
+            }
+            None => reader.string()?,
+        };
+        let value = match value_type {
+            TYPE_OBJECT => Value::Object(parse_binary_entries(reader, string_table)?),
+            TYPE_STRING => Value::String(reader.string()?),
+            TYPE_INT32 => Value::Int32(reader.u32()? as i32),
+            TYPE_FLOAT32 => Value::Float32(f32::from_bits(reader.u32()?)),
+            TYPE_UINT64 => Value::UInt64(reader.u64()?),
+            TYPE_INT64 => Value::Int64(reader.u64()? as i64),
+            _ => return Err(reader.error(&format!("unknown value type 0x{:02x}", value_type))),
+        };
+        entries.push((key, value));
+    }
+}
+
+#[allow(dead_code)]
+pub(crate) fn parse_binary(bytes: &[u8]) -> Result<Value, ParseError> {
+    let mut reader = ByteReader { bytes, offset: 0 };
+    Ok(Value::Object(parse_binary_entries(&mut reader, None)?))
+}
+
+fn write_binary_entries(entries: &[(String, Value)], output: &mut Vec<u8>) {
+    for (key, value) in entries {
+        let value_type = match value {
+            Value::Object(_) => TYPE_OBJECT,
+            Value::String(_) => TYPE_STRING,
+            Value::Int32(_) => TYPE_INT32,
+            Value::Float32(_) => TYPE_FLOAT32,
+            Value::UInt64(_) => TYPE_UINT64,
+            Value::Int64(_) => TYPE_INT64,
+        };
+        output.push(value_type);
+        output.extend_from_slice(key.as_bytes());
+        output.push(0);
+        match value {
+            Value::Object(children) => write_binary_entries(children, output),
+            Value::String(string) => {
+                output.extend_from_slice(string.as_bytes());
+                output.push(0);
+            }
+            Value::Int32(int) => output.extend_from_slice(&int.to_le_bytes()),
+            Value::Float32(float) => output.extend_from_slice(&float.to_le_bytes()),
+            Value::UInt64(int) => output.extend_from_slice(&int.to_le_bytes()),
+            Value::Int64(int) => output.extend_from_slice(&int.to_le_bytes()),
+        }
+    }
+    output.push(TYPE_END);
+}
+
+// the binary counterpart of to_string, as used by shortcuts.vdf
+#[allow(dead_code)]
+pub(crate) fn to_binary(value: &Value) -> Vec<u8> {
+    let mut output = Vec::new();
+    write_binary_entries(value.entries(), &mut output);
+    output
+}
+
+pub(crate) struct AppInfo {
+    pub app_id: u32,
+    pub data: Value,
+}
+
+const APPINFO_MAGIC_V27: u32 = 0x07564427;
+const APPINFO_MAGIC_V28: u32 = 0x07564428;
+const APPINFO_MAGIC_V29: u32 = 0x07564429;
+
+fn parse_string_table(bytes: &[u8], offset: usize) -> Result<Vec<String>, ParseError> {
+    let mut reader = ByteReader { bytes, offset };
+    let count = reader.u32()?;
+    (0..count).map(|_| reader.string()).collect()
+}
+
+// appinfo.vdf is Steam's cache of app metadata; it is only ever read since Steam rebuilds it itself
+pub(crate) fn parse_appinfo(bytes: &[u8]) -> Result<Vec<AppInfo>, ParseError> {
+    let mut reader = ByteReader { bytes, offset: 0 };
+    let magic = reader.u32()?;
+    if !matches!(magic, APPINFO_MAGIC_V27 | APPINFO_MAGIC_V28 | APPINFO_MAGIC_V29) {
+        return Err(ParseError::Binary { offset: 0, message: format!("unsupported appinfo.vdf version 0x{:08x}", magic) });
+    }
+    let _universe = reader.u32()?;
+
+    let string_table = if magic == APPINFO_MAGIC_V29 {
+        let offset = reader.u64()? as usize;
+        Some(parse_string_table(bytes, offset)?)
+    } else {
+        None
+    };
+
+    let mut apps = Vec::new();
+    loop {
+        let app_id = reader.u32()?;
+        if app_id == 0 {
+            return Ok(apps);
+        }
+        let size = reader.u32()? as usize;
+        let end = reader.offset + size;
+        // info state, last updated, PICS token, SHA-1 and change number, plus the SHA-1 of the binary data since v28
+        reader.take(if magic == APPINFO_MAGIC_V27 { 40 } else { 60 })?;
+
+        let data = Value::Object(parse_binary_entries(&mut reader, string_table.as_deref())?);
+        if reader.offset != end {
+            return Err(reader.error(&format!("app {} is {} bytes long but its data ends at {}", app_id, size, reader.offset)));
+        }
+        apps.push(AppInfo { app_id, data });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+
+    #[test]
+    fn parses_nested_objects() {
+        let config = parse(r#"
+            "UserLocalConfigStore"
+            {
+                "Software" { "Valve" { "Steam" { "apps" { "1091500" { "LaunchOptions" "PROTON_LOG=1 %command%" } } } } }
+            }
+        "#).unwrap();
+        let path = ["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", "1091500", "LaunchOptions"];
+        assert_eq!(config.get_path(&path), Some(&string("PROTON_LOG=1 %command%")));
+        // keys are looked up regardless of case
+        let lowercase = ["userlocalconfigstore", "software", "valve", "steam", "apps", "1091500", "launchoptions"];
+        assert_eq!(config.get_path(&lowercase), Some(&string("PROTON_LOG=1 %command%")));
+    }
+
+    #[test]
+    fn parses_escapes() {
+        let config = parse(r#""key" "a \"quoted\" C:\\path\twith\ntabs""#).unwrap();
+        assert_eq!(config.get("key"), Some(&string("a \"quoted\" C:\\path\twith\ntabs")));
+    }
+
+    #[test]
+    fn skips_comments() {
+        let config = parse("// written by Steam\n\"a\" \"1\" // trailing\n\"b\" { // opening\n\"c\" \"http://example.com\" }").unwrap();
+        assert_eq!(config, Value::Object(vec![
+            ("a".to_string(), string("1")),
+            ("b".to_string(), Value::Object(vec![("c".to_string(), string("http://example.com"))])),
+        ]));
+    }
+
+    #[test]
+    fn keeps_entries_of_every_platform() {
+        let config = parse("\"a\" \"windows\" [$WIN32]\n\"a\" \"other\" [!$WIN32]\n\"b\" { \"c\" \"d\" } [$LINUX]").unwrap();
+        assert_eq!(config.entries().len(), 3);
+        assert_eq!(config.get("a"), Some(&string("windows")));
+        assert_eq!(config.get_path(&["b", "c"]), Some(&string("d")));
+    }
+
+    #[test]
+    fn reports_the_line_of_errors() {
+        let error = parse("\"a\"\n{\n\"b\" \"c\"\n").unwrap_err();
+        assert!(matches!(error, ParseError::Text { line: 4, .. }), "{}", error);
+        assert!(parse("\"a\" \"unterminated").is_err());
+        assert!(parse("}").is_err());
+    }
+
+    #[test]
+    fn round_trips_text() {
+        let text = "\"a\"\n{\n\t\"b\"\t\t\"with \\\"quotes\\\", \\\\ and \\n\"\n\t\"c\"\n\t{\n\t}\n}\n";
+        let config = parse(text).unwrap();
+        assert_eq!(to_string(&config), text);
+        assert_eq!(parse(&to_string(&config)).unwrap(), config);
+    }
+
+    #[test]
+    fn set_path_reuses_keys_regardless_of_case() {
+        let mut config = parse("\"Apps\" { \"10\" { \"LaunchOptions\" \"old\" } }").unwrap();
+        config.set_path(&["apps", "10", "launchoptions"], string("new"));
+        config.set_path(&["apps", "20", "LaunchOptions"], string("added"));
+        assert_eq!(to_string(&config), "\"Apps\"\n{\n\t\"10\"\n\t{\n\t\t\"LaunchOptions\"\t\t\"new\"\n\t}\n\t\"20\"\n\t{\n\t\t\"LaunchOptions\"\t\t\"added\"\n\t}\n}\n");
+    }
+
+    const SHORTCUTS: &[u8] = include_bytes!("../tests/fixtures/steam/userdata/12345/config/shortcuts.vdf");
+
+    #[test]
+    fn parses_shortcuts() {
+        let shortcuts = parse_binary(SHORTCUTS).unwrap();
+        let shortcuts = shortcuts.get("shortcuts").unwrap();
+        assert_eq!(shortcuts.entries().len(), 2);
+        assert_eq!(shortcuts.get_path(&["0", "AppName"]), Some(&string("Heroic Games Launcher")));
+        // non-Steam app IDs are unsigned, but stored as a signed int32
+        assert_eq!(shortcuts.get_path(&["0", "appid"]), Some(&Value::Int32(-1294512761)));
+        assert_eq!(shortcuts.get_path(&["0", "tags", "0"]), Some(&string("favorite")));
+        assert_eq!(shortcuts.get_path(&["1", "LaunchOptions"]), Some(&string("PROTON_LOG=1 %command%")));
+        assert_eq!(shortcuts.get_path(&["1", "tags"]), Some(&Value::Object(Vec::new())));
+    }
+
+    #[test]
+    fn round_trips_shortcuts() {
+        let shortcuts = parse_binary(SHORTCUTS).unwrap();
+        assert_eq!(to_binary(&shortcuts), SHORTCUTS);
+    }
+
+    #[test]
+    fn round_trips_every_binary_type() {
+        let value = Value::Object(vec![
+            ("object".to_string(), Value::Object(vec![
+                ("string".to_string(), string("with spaces and ünicode")),
+                ("empty".to_string(), Value::Object(Vec::new())),
+            ])),
+            ("int32".to_string(), Value::Int32(-42)),
+            ("float32".to_string(), Value::Float32(1.5)),
+            ("uint64".to_string(), Value::UInt64(u64::MAX)),
+            ("int64".to_string(), Value::Int64(i64::MIN)),
+            ("empty string".to_string(), string("")),
+        ]);
+        assert_eq!(parse_binary(&to_binary(&value)).unwrap(), value);
+    }
+
+    #[test]
+    fn rejects_malformed_binary() {
+        assert!(parse_binary(&[TYPE_STRING, b'a', 0, b'b']).is_err());
+        assert!(parse_binary(&[TYPE_INT32, b'a', 0, 1, 2]).is_err());
+        assert!(parse_binary(&[0x05, b'a', 0]).is_err());
+    }
+
+    fn appinfo_header(magic: u32) -> Vec<u8> {
+        [magic.to_le_bytes(), 1u32.to_le_bytes()].concat()
+    }
+
+    fn appinfo_app(app_id: u32, header_length: usize, data: &[u8]) -> Vec<u8> {
+        let size = (header_length + data.len()) as u32;
+        [&app_id.to_le_bytes()[..], &size.to_le_bytes(), &vec![0; header_length], data].concat()
+    }
+
+    #[test]
+    fn parses_appinfo_v27() {
+        let data = [
+            &[TYPE_OBJECT][..], b"common\0",
+            &[TYPE_STRING], b"name\0Cyberpunk 2077\0",
+            &[TYPE_INT32], b"gameid\0", &1091500i32.to_le_bytes(),
+            &[TYPE_END, TYPE_END],
+        ].concat();
+        let bytes = [appinfo_header(APPINFO_MAGIC_V27), appinfo_app(1091500, 40, &data), 0u32.to_le_bytes().to_vec()].concat();
+
+        let apps = parse_appinfo(&bytes).unwrap();
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].app_id, 1091500);
+        assert_eq!(apps[0].data.get_path(&["common", "name"]), Some(&string("Cyberpunk 2077")));
+        assert_eq!(apps[0].data.get_path(&["common", "gameid"]), Some(&Value::Int32(1091500)));
+    }
+
+    #[test]
+    fn parses_appinfo_v29_string_table() {
+        let data = [
+            &[TYPE_OBJECT][..], &0u32.to_le_bytes(),
+            &[TYPE_STRING], &1u32.to_le_bytes(), b"Cyberpunk 2077\0",
+            &[TYPE_END, TYPE_END],
+        ].concat();
+        let apps = [appinfo_app(1091500, 60, &data), 0u32.to_le_bytes().to_vec()].concat();
+        let string_table_offset = (8 + 8 + apps.len()) as u64;
+        let bytes = [
+            appinfo_header(APPINFO_MAGIC_V29), string_table_offset.to_le_bytes().to_vec(), apps,
+            2u32.to_le_bytes().to_vec(), b"common\0name\0".to_vec(),
+        ].concat();
+
+        let apps = parse_appinfo(&bytes).unwrap();
+        assert_eq!(apps[0].data.get_path(&["common", "name"]), Some(&string("Cyberpunk 2077")));
+    }
+
+    #[test]
+    fn rejects_malformed_appinfo() {
+        // string table offsets pointing past the end, including ones that overflow when added to
+        for offset in [u64::MAX, u64::MAX - 2, 1 << 40] {
+            let bytes = [appinfo_header(APPINFO_MAGIC_V29), offset.to_le_bytes().to_vec()].concat();
+            assert!(parse_appinfo(&bytes).is_err());
+        }
+
+        let data = [&[TYPE_STRING][..], &7u32.to_le_bytes(), b"value\0", &[TYPE_END]].concat();
+        let apps = [appinfo_app(10, 60, &data), 0u32.to_le_bytes().to_vec()].concat();
+        let string_table_offset = (8 + 8 + apps.len()) as u64;
+        let bytes = [appinfo_header(APPINFO_MAGIC_V29), string_table_offset.to_le_bytes().to_vec(), apps, 0u32.to_le_bytes().to_vec()].concat();
+        assert!(parse_appinfo(&bytes).is_err());
+
+        assert!(parse_appinfo(&appinfo_header(0x12345678)).is_err());
+        assert!(parse_appinfo(&[0x27, 0x44]).is_err());
+        // an app whose size doesn't match its data
+        let mut bytes = [appinfo_header(APPINFO_MAGIC_V27), appinfo_app(10, 40, &[TYPE_END]), vec![0; 8]].concat();
+        bytes[12] += 1;
+        assert!(parse_appinfo(&bytes).is_err());
+    }
+}